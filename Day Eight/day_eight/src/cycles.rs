@@ -0,0 +1,303 @@
+use std::collections::{BTreeSet, HashMap};
+
+use num::integer::{ExtendedGcd, Integer};
+
+use crate::{GuidedMap, Unreachable};
+
+/// Where a ghost's walk settles into a loop, and when it's on a location ending in Z.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    /// Steps taken before the walk first reaches the part that repeats
+    pub tail: usize,
+    /// Steps to go round the repeating part once
+    pub length: usize,
+    /// Steps before `tail` that end on a Z
+    pub tail_hits: Vec<usize>,
+    /// Steps past `tail` that end on a Z, which happens again every `length` steps
+    pub offsets: Vec<usize>,
+}
+
+impl GhostCycle {
+    /// Whether the ghost is on a Z after `step` steps.
+    pub fn hits(&self, step: usize) -> bool {
+        if step < self.tail {
+            self.tail_hits.binary_search(&step).is_ok()
+        } else {
+            let offset = (step - self.tail) % self.length;
+            self.offsets.binary_search(&offset).is_ok()
+        }
+    }
+
+    pub fn never_hits(&self) -> bool {
+        self.tail_hits.is_empty() && self.offsets.is_empty()
+    }
+}
+
+impl GuidedMap {
+    pub fn ghost_cycle(&self, start: &str) -> Result<GhostCycle, Unreachable> {
+        Ok(self.cycle_from(self.location(start)?))
+    }
+
+    // Walks until it's somewhere it's been before at the same point in the instructions, from
+    // then on it does exactly what it did last time
+    pub(crate) fn cycle_from(&self, start_idx: usize) -> GhostCycle {
+        // When each (location, instruction index) was first reached
+        let mut first_seen = HashMap::new();
+        let mut hits = Vec::new();
+        let mut location = start_idx;
+        for (step, index, instruction) in self.instructions() {
+            if let Some(&tail) = first_seen.get(&(location, index)) {
+                let (tail_hits, cycle_hits): (Vec<_>, Vec<_>) =
+                    hits.into_iter().partition(|&hit| hit < tail);
+                return GhostCycle {
+                    tail,
+                    length: step - tail,
+                    tail_hits,
+                    offsets: cycle_hits.into_iter().map(|hit| hit - tail).collect(),
+                };
+            }
+            first_seen.insert((location, index), step);
+            if self.camel_map.locations[location].ends_with('Z') {
+                hits.push(step);
+            }
+            location = self.camel_map.step(location, instruction);
+        }
+        unreachable!("the instructions go round forever")
+    }
+}
+
+/// The first common step is further off than a `usize` can count, or than the working out can
+/// keep track of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooFar;
+
+/// The first step where every ghost is on a Z at once, `None` if there isn't one.
+pub fn first_common_hit(ghosts: &[GhostCycle]) -> Result<Option<usize>, TooFar> {
+    // Until the last ghost settles into its loop there's nothing better than checking each step
+    let settled = ghosts.iter().map(|ghost| ghost.tail).max().unwrap_or(0);
+    if let Some(step) = (0..settled).find(|&step| ghosts.iter().all(|ghost| ghost.hits(step))) {
+        return Ok(Some(step));
+    }
+
+    // After that every ghost is looping, so a step works if it lines up with one of each ghost's
+    // offsets. Every way of picking those is a set of congruences, and the ones with solutions
+    // leave one remainder each modulo the lcm of the loop lengths.
+    let mut modulus: i128 = 1;
+    let mut remainders = BTreeSet::from([0]);
+    for ghost in ghosts {
+        let length = ghost.length as i128;
+        // Everything `crt` works out stays below the new modulus, so this is the only check needed
+        let combined_modulus = (modulus / modulus.gcd(&length))
+            .checked_mul(length)
+            .ok_or(TooFar)?;
+        let mut combined = BTreeSet::new();
+        for &remainder in &remainders {
+            for &offset in &ghost.offsets {
+                let target = (ghost.tail + offset) as i128 % length;
+                combined.extend(crt(remainder, modulus, target, length));
+            }
+        }
+        if combined.is_empty() {
+            return Ok(None);
+        }
+        modulus = combined_modulus;
+        remainders = combined;
+    }
+
+    // The first step from `settled` on with any of the remainders
+    let settled = settled as i128;
+    let step = remainders
+        .into_iter()
+        .map(|remainder| {
+            let loops = ((settled - remainder).max(0) + modulus - 1) / modulus;
+            loops
+                .checked_mul(modulus)
+                .and_then(|past| past.checked_add(remainder))
+        })
+        .min()
+        .flatten()
+        .ok_or(TooFar)?;
+    usize::try_from(step).map(Some).map_err(|_| TooFar)
+}
+
+// The x in `0..lcm(m1, m2)` with x = a1 (mod m1) and x = a2 (mod m2), if there is one. The moduli
+// don't have to be coprime, but their lcm has to fit.
+fn crt(a1: i128, m1: i128, a2: i128, m2: i128) -> Option<i128> {
+    let ExtendedGcd { gcd, x, .. } = m1.extended_gcd(&m2);
+    let difference = a2 - a1;
+    if difference % gcd != 0 {
+        return None;
+    }
+    // m1 x = gcd (mod m2), so stepping a1 on by m1 k for this k lands on a2. Both sides are below
+    // m2 / gcd, which came from a usize, so their product fits in a u128.
+    let n = m2 / gcd;
+    let k = (difference / gcd).rem_euclid(n) as u128 * x.rem_euclid(n) as u128 % n as u128;
+    Some((a1 + m1 * k as i128).rem_euclid(m1 / gcd * m2))
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use num::Integer;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{crt, first_common_hit, GhostCycle, TooFar};
+    use crate::{GuidedMap, Unreachable};
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt(2, 3, 3, 5), Some(8));
+        assert_eq!(crt(0, 1, 4, 7), Some(4));
+        // Sharing a factor is fine as long as they agree on it
+        assert_eq!(crt(1, 4, 3, 6), Some(9));
+        assert_eq!(crt(0, 4, 1, 6), None);
+        // Lengths as long as they come
+        let big = usize::MAX as i128;
+        assert_eq!(crt(0, 1, big - 1, big), Some(big - 1));
+        assert_eq!(crt(big - 1, big, 0, 1), Some(big - 1));
+    }
+
+    #[test]
+    fn too_far() {
+        let ghost = |length: usize, offset: usize| GhostCycle {
+            tail: 0,
+            length,
+            tail_hits: vec![],
+            offsets: vec![offset],
+        };
+        // The lcm alone is past i128
+        let ghosts = [ghost(usize::MAX, 0), ghost(usize::MAX - 1, 1)];
+        assert_eq!(first_common_hit(&ghosts), Err(TooFar));
+        // The lcm fits but the step it lands on doesn't fit a usize
+        let ghosts = [ghost(1 << 62, 1), ghost((1 << 62) - 1, 0)];
+        assert_eq!(first_common_hit(&ghosts), Err(TooFar));
+        // Big loops are fine as long as the answer isn't
+        let ghosts = [ghost(1 << 62, 5), ghost((1 << 62) - 1, 5)];
+        assert_eq!(first_common_hit(&ghosts), Ok(Some(5)));
+    }
+
+    #[test]
+    fn analyse() {
+        // One loops straight back to Z, the other has a run in before its loop and hits Z part
+        // way round, so the first hits (2 and 1) have an lcm of 2 but the real answer is 5
+        let guided_map = GuidedMap::from_str(
+            "L\n\n1AA = (1BB, 1BB)\n1BB = (1ZZ, 1ZZ)\n1ZZ = (1CC, 1CC)\n1CC = (1BB, 1BB)\n\
+             2AA = (2ZZ, 2ZZ)\n2ZZ = (2BB, 2BB)\n2BB = (2ZZ, 2ZZ)",
+        )
+        .unwrap();
+        assert_eq!(
+            guided_map.ghost_cycle("1AA").unwrap(),
+            GhostCycle {
+                tail: 1,
+                length: 3,
+                tail_hits: vec![],
+                offsets: vec![1]
+            }
+        );
+        assert_eq!(
+            guided_map.ghost_cycle("2AA").unwrap(),
+            GhostCycle {
+                tail: 1,
+                length: 2,
+                tail_hits: vec![],
+                offsets: vec![0]
+            }
+        );
+        assert_eq!(guided_map.a_to_z(), Ok(5));
+
+        // Z only turns up before the loop
+        let guided_map = GuidedMap::from_str(
+            "L\n\n3AA = (3ZZ, 3ZZ)\n3ZZ = (3BB, 3BB)\n3BB = (3BB, 3BB)\n4AA = (4BB, 4BB)\n\
+             4BB = (4ZZ, 4ZZ)\n4ZZ = (4BB, 4BB)",
+        )
+        .unwrap();
+        assert_eq!(guided_map.ghost_cycle("3AA").unwrap().tail_hits, [1]);
+        assert_eq!(
+            guided_map.a_to_z(),
+            Err(Unreachable::NeverTogether {
+                starts: vec!["3AA".to_string(), "4AA".to_string()]
+            })
+        );
+        assert_eq!(
+            guided_map.a_to_z().unwrap_err().to_string(),
+            "the ghosts starting at `3AA`, `4AA` are never all on a location ending in `Z` at once"
+        );
+    }
+
+    #[test]
+    fn puzzle_example() {
+        let guided_map = GuidedMap::from_str(
+            "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n\
+             22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)",
+        )
+        .unwrap();
+        assert_eq!(guided_map.a_to_z(), Ok(6));
+    }
+
+    // Steps every ghost together until they line up. Every ghost has to take its step even
+    // once one has missed, so this counts the misses rather than stopping at the first.
+    fn walk_together(guided_map: &GuidedMap, limit: usize) -> Option<usize> {
+        let mut walks = guided_map
+            .camel_map
+            .locations
+            .iter()
+            .filter(|location| location.ends_with('A'))
+            .map(|location| guided_map.walk(location).unwrap())
+            .collect::<Vec<_>>();
+        (0..limit).find(|_| {
+            walks
+                .iter_mut()
+                .map(|walk| walk.next().unwrap().ends_with('Z'))
+                .filter(|&hit| !hit)
+                .count()
+                == 0
+        })
+    }
+
+    #[test]
+    fn matches_walking() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let mut next = move |below: usize| rng.gen_range(0..below);
+
+        for _ in 0..300 {
+            let instructions = (0..1 + next(4))
+                .map(|_| if next(2) == 0 { 'L' } else { 'R' })
+                .collect::<String>();
+            let names = (0..4 + next(6))
+                .map(|index| format!("{index}{}", ['A', 'B', 'Z'][next(3)]))
+                .collect::<Vec<_>>();
+            let map = names
+                .iter()
+                .map(|name| {
+                    let left = &names[next(names.len())];
+                    let right = &names[next(names.len())];
+                    format!("{name} = ({left}, {right})")
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            let guided_map = GuidedMap::from_str(&format!("{instructions}\n\n{map}")).unwrap();
+
+            let ghosts = guided_map
+                .camel_map
+                .locations
+                .iter()
+                .filter(|location| location.ends_with('A'))
+                .map(|location| guided_map.ghost_cycle(location).unwrap())
+                .collect::<Vec<_>>();
+            // Everything has repeated by the time every ghost is in its loop and they've all
+            // been round a whole number of times
+            let settled = ghosts.iter().map(|ghost| ghost.tail).max().unwrap_or(0);
+            let period = ghosts
+                .iter()
+                .fold(1, |period, ghost| period.lcm(&ghost.length));
+            let walked = walk_together(&guided_map, settled + period);
+            assert_eq!(
+                first_common_hit(&ghosts),
+                Ok(walked),
+                "{instructions}\n\n{map}"
+            );
+        }
+    }
+}
@@ -0,0 +1,489 @@
+use std::{collections::HashSet, str::FromStr};
+
+use aoc_core::{Answer, Line, ParseError, PartError, Solution};
+use indexmap::IndexSet;
+use itertools::Itertools;
+
+pub mod cycles;
+
+pub use cycles::{GhostCycle, TooFar};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Left,
+    Right,
+}
+
+impl Instruction {
+    fn from_char(c: char) -> Option<Instruction> {
+        match c {
+            'L' => Some(Instruction::Left),
+            'R' => Some(Instruction::Right),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Instructions {
+    instructions: Vec<Instruction>,
+}
+
+impl Instructions {
+    fn from_line(line: Line) -> Result<Instructions, ParseError> {
+        if line.text.is_empty() {
+            return Err(line.error_at_end("at least one instruction"));
+        }
+        let instructions = line
+            .text
+            .char_indices()
+            .map(|(index, c)| {
+                Instruction::from_char(c).ok_or_else(|| {
+                    line.error_at(&line.text[index..index + c.len_utf8()], "`L` or `R`")
+                })
+            })
+            .try_collect()?;
+        Ok(Instructions { instructions })
+    }
+}
+
+/// The instructions over and over forever, as `(step_number, instruction_index, instruction)`
+/// with steps counted from 0.
+#[derive(Debug, Clone)]
+pub struct InstructionIterator<'a> {
+    instructions: &'a Instructions,
+    step: usize,
+}
+
+impl Iterator for InstructionIterator<'_> {
+    type Item = (usize, usize, Instruction);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.step % self.instructions.instructions.len();
+        let item = (self.step, index, self.instructions.instructions[index]);
+        self.step += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<'a> IntoIterator for &'a Instructions {
+    type Item = (usize, usize, Instruction);
+    type IntoIter = InstructionIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        InstructionIterator {
+            instructions: self,
+            step: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CamelMap {
+    locations: IndexSet<Box<str>>,
+    map: Vec<Location>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Location {
+    left: usize,
+    right: usize,
+}
+
+impl CamelMap {
+    // Where following `instruction` from `location` leads
+    fn step(&self, location: usize, instruction: Instruction) -> usize {
+        match instruction {
+            Instruction::Left => self.map[location].left,
+            Instruction::Right => self.map[location].right,
+        }
+    }
+}
+
+/// Every location visited following the instructions, starting with where it starts. Never ends.
+#[derive(Debug, Clone)]
+pub struct Walk<'a> {
+    camel_map: &'a CamelMap,
+    instructions: InstructionIterator<'a>,
+    location: usize,
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let location = self.location;
+        let (_, _, instruction) = self.instructions.next()?;
+        self.location = self.camel_map.step(location, instruction);
+        Some(&self.camel_map.locations[location])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl FromStr for GuidedMap {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<GuidedMap, ParseError> {
+        let mut lines = aoc_core::parse::lines(8, s);
+        let instructions = match lines.next() {
+            Some(line) => Instructions::from_line(line)?,
+            None => return Err(ParseError::missing_line(8, 1, "a line of instructions")),
+        };
+        if let Some(line) = lines.next() {
+            if !line.text.is_empty() {
+                return Err(line.error_at(line.text, "a blank line"));
+            }
+        }
+
+        let mut locations = IndexSet::<Box<str>>::new();
+        let mut map = Vec::<(Line, &str, &str)>::new();
+        for line in lines {
+            let (location, connections) = line.split_once(line.text, " = ")?;
+            let connections = line.expect_prefix(connections, "(")?;
+            let connections = connections
+                .strip_suffix(')')
+                .ok_or_else(|| line.error_at_end("`)`"))?;
+            let (left, right) = line.split_once(connections, ", ")?;
+
+            // The map is indexed the same as the locations so every location can only appear once
+            if !locations.insert(location.into()) {
+                return Err(line.error_at(location, "a location that hasn't been mapped yet"));
+            }
+            map.push((line, left, right));
+        }
+
+        let find = |line: Line, location: &str| {
+            locations
+                .get_index_of(location)
+                .ok_or_else(|| line.error_at(location, "a location defined in the map"))
+        };
+        let map = map
+            .into_iter()
+            .map(|(line, left, right)| {
+                Ok(Location {
+                    left: find(line, left)?,
+                    right: find(line, right)?,
+                })
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+
+        Ok(GuidedMap {
+            instructions,
+            camel_map: CamelMap { locations, map },
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GuidedMap {
+    instructions: Instructions,
+    camel_map: CamelMap,
+}
+
+impl GuidedMap {
+    pub fn instructions(&self) -> InstructionIterator<'_> {
+        self.instructions.into_iter()
+    }
+
+    /// Follows the instructions from `start`, `None` if there's no such location.
+    pub fn walk(&self, start: &str) -> Option<Walk<'_>> {
+        Some(Walk {
+            camel_map: &self.camel_map,
+            instructions: self.instructions(),
+            location: self.camel_map.locations.get_index_of(start)?,
+        })
+    }
+
+    pub fn route(&self, start: &str, end: &str) -> Result<usize, Unreachable> {
+        let start_idx = self.location(start)?;
+        let end_idx = self.location(end)?;
+        self.steps_from(start_idx, end_idx)
+    }
+
+    fn location(&self, name: &str) -> Result<usize, Unreachable> {
+        self.camel_map
+            .locations
+            .get_index_of(name)
+            .ok_or_else(|| Unreachable::UnknownLocation(name.to_string()))
+    }
+
+    fn steps_from(&self, start_idx: usize, end_idx: usize) -> Result<usize, Unreachable> {
+        match self.first_hit(start_idx, |location| location == end_idx) {
+            Ok((steps, _, _)) => Ok(steps),
+            Err(steps) => Err(Unreachable::Cycle {
+                from: self.camel_map.locations[start_idx].to_string(),
+                to: format!("`{}`", self.camel_map.locations[end_idx]),
+                steps,
+            }),
+        }
+    }
+
+    /// The first step where every ghost starting on a location ending in A is on one ending in Z.
+    pub fn a_to_z(&self) -> Result<usize, Unreachable> {
+        let start_indexes = self
+            .camel_map
+            .locations
+            .iter()
+            .enumerate()
+            .filter(|(_, location)| location.ends_with('A'))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        if start_indexes.is_empty() {
+            return Err(Unreachable::NoStarts);
+        }
+
+        let ghosts = start_indexes
+            .iter()
+            .map(|&start_idx| {
+                let ghost = self.cycle_from(start_idx);
+                if ghost.never_hits() {
+                    return Err(Unreachable::Cycle {
+                        from: self.camel_map.locations[start_idx].to_string(),
+                        to: "a location ending in `Z`".to_string(),
+                        steps: ghost.tail + ghost.length,
+                    });
+                }
+                Ok(ghost)
+            })
+            .collect::<Result<Vec<_>, Unreachable>>()?;
+
+        let starts = || {
+            start_indexes
+                .iter()
+                .map(|&idx| self.camel_map.locations[idx].to_string())
+                .collect()
+        };
+        match cycles::first_common_hit(&ghosts) {
+            Ok(Some(step)) => Ok(step),
+            Ok(None) => Err(Unreachable::NeverTogether { starts: starts() }),
+            Err(TooFar) => Err(Unreachable::TooFar { starts: starts() }),
+        }
+    }
+
+    // Walks from `start_idx` until `found` says so, giving the steps taken, the instruction due
+    // next and where it stopped. Being somewhere for the second time at the same point in the
+    // instructions means it's going round in circles, so that gives up with how many steps it
+    // took to get back there.
+    fn first_hit(
+        &self,
+        start_idx: usize,
+        found: impl Fn(usize) -> bool,
+    ) -> Result<(usize, usize, usize), usize> {
+        // (location, instruction index) pairs already walked through
+        let mut seen = HashSet::new();
+        let mut location = start_idx;
+        for (step, index, instruction) in self.instructions() {
+            if found(location) {
+                return Ok((step, index, location));
+            }
+            if !seen.insert((location, index)) {
+                return Err(step);
+            }
+            location = self.camel_map.step(location, instruction);
+        }
+        unreachable!("the instructions go round forever")
+    }
+}
+
+/// Why a route couldn't be followed to the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unreachable {
+    /// The map doesn't have a location with this name
+    UnknownLocation(String),
+    /// Following the instructions from `from` starts repeating itself after `steps` steps
+    /// without ever getting to `to`
+    Cycle {
+        from: String,
+        to: String,
+        steps: usize,
+    },
+    /// Every ghost gets to a Z, but never all at the same time
+    NeverTogether { starts: Vec<String> },
+    /// The ghosts do line up, but further on than a `usize` can count
+    TooFar { starts: Vec<String> },
+    /// No location ends in A, so there are no ghosts to follow
+    NoStarts,
+}
+
+impl std::fmt::Display for Unreachable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unreachable::UnknownLocation(location) => {
+                write!(f, "the map doesn't have a `{location}` location")
+            }
+            Unreachable::Cycle { from, to, steps } => write!(
+                f,
+                "can't get from `{from}` to {to}: the route repeats itself after {steps} steps"
+            ),
+            Unreachable::NeverTogether { starts } => write!(
+                f,
+                "the ghosts starting at `{}` are never all on a location ending in `Z` at once",
+                starts.join("`, `")
+            ),
+            Unreachable::TooFar { starts } => write!(
+                f,
+                "the ghosts starting at `{}` only all get to a location ending in `Z` after more \
+                 steps than can be counted",
+                starts.join("`, `")
+            ),
+            Unreachable::NoStarts => write!(f, "no location ends in `A`, so there are no ghosts"),
+        }
+    }
+}
+
+impl std::error::Error for Unreachable {}
+
+impl Solution for GuidedMap {
+    const DAY: u8 = 8;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, PartError> {
+        Ok(self.route("AAA", "ZZZ")?.into())
+    }
+
+    fn part_two(&self) -> Result<Answer, PartError> {
+        Ok(self.a_to_z()?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    #[test]
+    fn test() {
+        let guided_map = super::GuidedMap::from_str(include_str!("../test_input.txt")).unwrap();
+        assert_eq!(guided_map.route("AAA", "ZZZ"), Ok(2));
+    }
+
+    #[test]
+    fn instructions() {
+        use super::Instruction::{Left, Right};
+
+        let guided_map = super::GuidedMap::from_str(include_str!("../test_input.txt")).unwrap();
+        assert_eq!(
+            guided_map.instructions().take(5).collect::<Vec<_>>(),
+            [
+                (0, 0, Right),
+                (1, 1, Left),
+                (2, 0, Right),
+                (3, 1, Left),
+                (4, 0, Right)
+            ]
+        );
+        // It keeps going long past the end of the list
+        assert_eq!(guided_map.instructions().nth(1001), Some((1001, 1, Left)));
+    }
+
+    #[test]
+    fn walk() {
+        let guided_map = super::GuidedMap::from_str(include_str!("../test_input.txt")).unwrap();
+        assert_eq!(
+            guided_map.walk("AAA").unwrap().take(4).collect::<Vec<_>>(),
+            ["AAA", "CCC", "ZZZ", "ZZZ"]
+        );
+        assert!(guided_map.walk("XXX").is_none());
+
+        // Going round the instructions twice
+        let guided_map = super::GuidedMap::from_str(
+            "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)",
+        )
+        .unwrap();
+        let walk = guided_map.walk("AAA").unwrap();
+        assert_eq!(walk.clone().position(|location| location == "ZZZ"), Some(6));
+        assert_eq!(guided_map.route("AAA", "ZZZ"), Ok(6));
+        assert_eq!(
+            walk.take(7).collect::<Vec<_>>(),
+            ["AAA", "BBB", "AAA", "BBB", "AAA", "BBB", "ZZZ"]
+        );
+    }
+
+    #[test]
+    fn unreachable() {
+        use aoc_core::Solution;
+
+        use super::Unreachable;
+
+        // AAA and BBB only lead to each other
+        let guided_map = super::GuidedMap::from_str(
+            "LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)",
+        )
+        .unwrap();
+        let err = guided_map.route("AAA", "ZZZ").unwrap_err();
+        assert_eq!(
+            err,
+            Unreachable::Cycle {
+                from: "AAA".to_string(),
+                to: "`ZZZ`".to_string(),
+                steps: 2
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "can't get from `AAA` to `ZZZ`: the route repeats itself after 2 steps"
+        );
+        assert_eq!(guided_map.route("AAA", "BBB"), Ok(1));
+        assert_eq!(
+            guided_map.route("AAA", "YYY"),
+            Err(Unreachable::UnknownLocation("YYY".to_string()))
+        );
+        assert!(matches!(
+            guided_map.a_to_z(),
+            Err(Unreachable::Cycle { steps: 2, .. })
+        ));
+        // A part that can't get there fails rather than answering with the reason
+        assert_eq!(guided_map.part_one(), Err(err.into()));
+
+        let guided_map =
+            super::GuidedMap::from_str("L\n\nBBB = (ZZZ, ZZZ)\nZZZ = (BBB, BBB)").unwrap();
+        assert_eq!(guided_map.a_to_z(), Err(Unreachable::NoStarts));
+
+        // The same location again but at a different point in the instructions isn't a loop yet
+        let guided_map = super::GuidedMap::from_str(
+            "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)",
+        )
+        .unwrap();
+        assert_eq!(guided_map.a_to_z(), Ok(6));
+    }
+
+    #[test]
+    fn long_routes() {
+        // Far more steps than there's stack for if every step were a call
+        let mut input = "L\n\n".to_string();
+        for location in 0..200_000 {
+            input += &format!("N{location} = (N{}, N{location})\n", location + 1);
+        }
+        input += "N200000 = (N200000, N200000)";
+        let guided_map = super::GuidedMap::from_str(&input).unwrap();
+        assert_eq!(guided_map.route("N0", "N200000"), Ok(200_000));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = super::GuidedMap::from_str("RLX\n\nAAA = (AAA, AAA)").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 8, line 1, column 3: expected `L` or `R`, found `X`"
+        );
+
+        let err = super::GuidedMap::from_str("RL\n\nAAA = (BBB, AAA)").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 8));
+
+        let err = super::GuidedMap::from_str("RL\n\nAAA = (AAA AAA)").unwrap_err();
+        assert_eq!(err.expected, "`, `");
+
+        let err =
+            super::GuidedMap::from_str("RL\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)").unwrap_err();
+        assert_eq!(err.line, 4);
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+
+use crate::SeedMapSection;
+
+/// The almanac's sections keyed by the category they convert from.
+#[derive(Debug, Clone, Default)]
+pub struct CategoryGraph {
+    // Category name to the indices of every section converting out of it
+    edges: IndexMap<String, Vec<usize>>,
+}
+
+impl CategoryGraph {
+    pub fn new(sections: &[SeedMapSection]) -> CategoryGraph {
+        let mut edges: IndexMap<String, Vec<usize>> = IndexMap::new();
+        for (index, section) in sections.iter().enumerate() {
+            edges.entry(section.from.clone()).or_default().push(index);
+            edges.entry(section.to.clone()).or_default();
+        }
+        CategoryGraph { edges }
+    }
+
+    pub fn contains(&self, category: &str) -> bool {
+        self.edges.contains_key(category)
+    }
+
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        self.edges.keys().map(String::as_str)
+    }
+
+    /// The sections to apply, in order, to convert `from` into `to` by following `to` fields.
+    pub fn path(
+        &self,
+        sections: &[SeedMapSection],
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<usize>, ConversionError> {
+        for category in [from, to] {
+            if !self.contains(category) {
+                return Err(ConversionError::UnknownCategory(category.to_string()));
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut seen = HashSet::new();
+        let mut current = from;
+        while current != to {
+            let broken = || ConversionError::Broken {
+                from: from.to_string(),
+                to: to.to_string(),
+                stuck_at: current.to_string(),
+            };
+            // Going round in circles is as good as running out of maps
+            if !seen.insert(current) {
+                return Err(broken());
+            }
+            match self.edges[current].as_slice() {
+                [] => return Err(broken()),
+                [index] => {
+                    path.push(*index);
+                    current = &sections[*index].to;
+                }
+                many => {
+                    return Err(ConversionError::Ambiguous {
+                        category: current.to_string(),
+                        choices: many.iter().map(|&i| sections[i].to.clone()).collect(),
+                    })
+                }
+            }
+        }
+        Ok(path)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+    UnknownCategory(String),
+    /// Following the maps from `from` runs out at `stuck_at` without reaching `to`
+    Broken {
+        from: String,
+        to: String,
+        stuck_at: String,
+    },
+    /// More than one map converts out of `category`
+    Ambiguous {
+        category: String,
+        choices: Vec<String>,
+    },
+    /// The only way there is backwards through maps that aren't one-to-one
+    NotInvertible {
+        from: String,
+        to: String,
+    },
+}
+
+impl std::fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConversionError::UnknownCategory(category) => {
+                write!(f, "no map mentions the `{category}` category")
+            }
+            ConversionError::Broken { from, to, stuck_at } => write!(
+                f,
+                "can't convert `{from}` to `{to}`: nothing converts `{stuck_at}` any further"
+            ),
+            ConversionError::Ambiguous { category, choices } => write!(
+                f,
+                "`{category}` converts to more than one category: `{}`",
+                choices.join("`, `")
+            ),
+            ConversionError::NotInvertible { from, to } => write!(
+                f,
+                "can't convert `{from}` to `{to}`: the `{to}` to `{from}` maps aren't one-to-one"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+#[cfg(test)]
+mod test {
+    use super::ConversionError;
+    use crate::Almanac;
+
+    fn almanac() -> Almanac {
+        include_str!("../test_input.txt").parse().unwrap()
+    }
+
+    #[test]
+    fn forwards() {
+        let almanac = almanac();
+        let soil_to_humidity = almanac.conversion("soil", "humidity").unwrap();
+        for soil in 0..120 {
+            let expected = almanac.seed_maps[1..6]
+                .iter()
+                .fold(soil, |value, map| map.get_seed(value));
+            assert_eq!(soil_to_humidity.get(soil), expected);
+        }
+    }
+
+    #[test]
+    fn backwards() {
+        let almanac = almanac();
+        let seed_to_location = almanac.conversion("seed", "location").unwrap();
+        let location_to_seed = almanac.conversion("location", "seed").unwrap();
+        for seed in [79, 14, 55, 13, 0, 99, 1000] {
+            assert_eq!(location_to_seed.get(seed_to_location.get(seed)), seed);
+        }
+        assert_eq!(location_to_seed.get(46), 82);
+        assert_eq!(almanac.seeds_for_location(46).unwrap().to_vec(), [82]);
+
+        // Single sections go backwards too
+        let seed_to_soil = &almanac.seed_maps[0];
+        assert_eq!(seed_to_soil.get_sources(81).to_vec(), [79]);
+        assert_eq!(seed_to_soil.get_sources(50).to_vec(), [98]);
+        assert_eq!(seed_to_soil.get_sources(99).to_vec(), [97]);
+    }
+
+    #[test]
+    fn errors() {
+        let almanac = almanac();
+        assert_eq!(
+            almanac.conversion("seed", "cheese").unwrap_err(),
+            ConversionError::UnknownCategory("cheese".to_string())
+        );
+
+        let almanac: Almanac = "seeds: 1\n\na-to-b map:\n0 0 1\n\nc-to-d map:\n0 0 1"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            almanac.conversion("a", "d").unwrap_err().to_string(),
+            "can't convert `a` to `d`: nothing converts `b` any further"
+        );
+
+        let almanac: Almanac = "seeds: 1\n\na-to-b map:\n0 0 1\n\na-to-c map:\n0 0 1"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            almanac.conversion("a", "c").unwrap_err(),
+            ConversionError::Ambiguous {
+                category: "a".to_string(),
+                choices: vec!["b".to_string(), "c".to_string()]
+            }
+        );
+
+        // 0 and 1 both end up at 5, so there's no going back
+        let almanac: Almanac = "seeds: 1\n\na-to-b map:\n5 0 1\n5 1 1".parse().unwrap();
+        assert!(almanac.conversion("a", "b").is_ok());
+        assert!(matches!(
+            almanac.conversion("b", "a"),
+            Err(ConversionError::NotInvertible { .. })
+        ));
+    }
+}
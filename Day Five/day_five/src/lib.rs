@@ -0,0 +1,408 @@
+use std::{ops::Range, str::FromStr};
+
+use aoc_core::{Answer, ParseError, PartError, Solution};
+use range_collections::{range_set::RangeSetRange, RangeSet};
+use smallvec::SmallVec;
+
+pub mod graph;
+pub mod part1;
+pub mod part2;
+pub mod piecewise;
+
+use graph::{CategoryGraph, ConversionError};
+use piecewise::PiecewiseMap;
+
+// Most sections split a range into a handful of pieces, so keep them inline
+pub type SeedRanges = RangeSet<[u64; 16]>;
+
+#[derive(Debug, Clone)]
+pub struct SeedMapSection {
+    pub from: String,
+    pub to: String,
+    pub map_values: Vec<(u64, u64, u64)>,
+}
+
+impl SeedMapSection {
+    pub fn get_seed(&self, seed: u64) -> u64 {
+        for (src, src_max, len) in &self.map_values {
+            if src <= &seed && src_max > &seed {
+                return len + (seed - src);
+            }
+        }
+        seed
+    }
+
+    /// Adds a `dest src len` mapping, unless either end of it runs past `u64::MAX`.
+    pub fn add(&mut self, dest: u64, src: u64, len: u64) -> Result<(), RangeOverflow> {
+        let src_max = src.checked_add(len).ok_or(RangeOverflow)?;
+        dest.checked_add(len).ok_or(RangeOverflow)?;
+        self.map_values.push((src, src_max, dest));
+        Ok(())
+    }
+
+    /// Every value in this section's source category that maps onto `value`.
+    pub fn get_sources(&self, value: u64) -> SmallVec<[u64; 2]> {
+        PiecewiseMap::from_section(self).preimage(value)
+    }
+
+    /// Every value in this section's source category that maps into `ranges`.
+    pub fn preimage_ranges(&self, ranges: &SeedRanges) -> SeedRanges {
+        PiecewiseMap::from_section(self).preimage_ranges(ranges)
+    }
+
+    pub fn map_range(&self, range: Range<u64>) -> SeedRanges {
+        self.map_ranges(&SeedRanges::from(range))
+    }
+
+    // Splits the ranges at the section's boundaries and shifts every piece that a mapping covers.
+    // Anything no mapping covers passes through unchanged.
+    pub fn map_ranges(&self, ranges: &SeedRanges) -> SeedRanges {
+        let mut unmapped = ranges.clone();
+        let mut mapped = SeedRanges::empty();
+
+        for &(src, src_max, dest) in &self.map_values {
+            let window = SeedRanges::from(src..src_max);
+            for range in bounded(&(&unmapped & &window)) {
+                mapped |= SeedRanges::from(dest + (range.start - src)..dest + (range.end - src));
+            }
+            unmapped -= window;
+        }
+
+        mapped | unmapped
+    }
+}
+
+/// A map range that would wrap round past `u64::MAX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeOverflow;
+
+impl std::fmt::Display for RangeOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "map range runs past u64::MAX")
+    }
+}
+
+impl std::error::Error for RangeOverflow {}
+
+/// Why there's no lowest location for the seeds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeedError {
+    /// Nothing gets planted, every range is empty
+    NoSeeds,
+    /// The last start has no length to go with it
+    Unpaired,
+    /// The seed range with this 1 based number runs past `u64::MAX`
+    PastMax(usize),
+    /// The maps don't get from seeds to locations
+    Conversion(ConversionError),
+}
+
+impl std::fmt::Display for SeedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SeedError::NoSeeds => write!(f, "there are no seeds to plant"),
+            SeedError::Unpaired => {
+                write!(f, "the seeds don't pair up into (start, length) ranges")
+            }
+            SeedError::PastMax(range) => write!(f, "seed range {range} runs past u64::MAX"),
+            SeedError::Conversion(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for SeedError {}
+
+impl From<ConversionError> for SeedError {
+    fn from(err: ConversionError) -> SeedError {
+        SeedError::Conversion(err)
+    }
+}
+
+// Every range we build has an end, so the open-ended variant never turns up
+pub(crate) fn bounded(ranges: &SeedRanges) -> impl Iterator<Item = Range<u64>> + '_ {
+    ranges.iter().map(|range| match range {
+        RangeSetRange::Range(range) => *range.start..*range.end,
+        RangeSetRange::RangeFrom(_) => unreachable!("seed ranges are always bounded"),
+    })
+}
+
+// The seeds to plant and every map section in the order they appear in the almanac
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub seed_maps: Vec<SeedMapSection>,
+    graph: CategoryGraph,
+}
+
+impl Almanac {
+    pub fn graph(&self) -> &CategoryGraph {
+        &self.graph
+    }
+
+    /// One function converting any `from` category into `to`.
+    ///
+    /// Follows the sections' `from`/`to` names forwards, and when `to` comes before `from` walks
+    /// the chain the other way and inverts it.
+    pub fn conversion(&self, from: &str, to: &str) -> Result<PiecewiseMap, ConversionError> {
+        match self.chain(from, to) {
+            Err(err @ ConversionError::Broken { .. }) => match self.chain(to, from) {
+                Ok(map) => map.inverse().ok_or_else(|| ConversionError::NotInvertible {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                }),
+                Err(_) => Err(err),
+            },
+            result => result,
+        }
+    }
+
+    fn chain(&self, from: &str, to: &str) -> Result<PiecewiseMap, ConversionError> {
+        let path = self.graph.path(&self.seed_maps, from, to)?;
+        Ok(path
+            .into_iter()
+            .fold(PiecewiseMap::identity(), |map, index| {
+                map.compose(&PiecewiseMap::from_section(&self.seed_maps[index]))
+            }))
+    }
+
+    // The seed line read as (start, length) pairs, the way part two wants it
+    pub fn seed_pairs(&self) -> Result<Vec<Range<u64>>, SeedError> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(SeedError::Unpaired);
+        }
+        self.seeds
+            .chunks_exact(2)
+            .enumerate()
+            .map(|(index, pair)| {
+                let end = pair[0]
+                    .checked_add(pair[1])
+                    .ok_or(SeedError::PastMax(index + 1))?;
+                Ok(pair[0]..end)
+            })
+            .collect()
+    }
+
+    // Every seed part two plants, as one set
+    pub fn seed_ranges(&self) -> Result<SeedRanges, SeedError> {
+        Ok(self
+            .seed_pairs()?
+            .into_iter()
+            .map(SeedRanges::from)
+            .fold(SeedRanges::empty(), |ranges, range| ranges | range))
+    }
+
+    /// Which seeds end up at `location`, handy when a location looks wrong.
+    pub fn seeds_for_location(&self, location: u64) -> Result<SmallVec<[u64; 2]>, ConversionError> {
+        Ok(self.location_map()?.preimage(location))
+    }
+
+    // Every section squashed into one seed to location function
+    pub fn location_map(&self) -> Result<PiecewiseMap, ConversionError> {
+        self.conversion("seed", "location")
+    }
+}
+
+impl FromStr for Almanac {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Almanac, ParseError> {
+        let mut lines = aoc_core::parse::lines(5, s);
+
+        let Some(line) = lines.next() else {
+            return Err(ParseError::missing_line(5, 1, "`seeds:`"));
+        };
+        let seed_list = line.expect_prefix(line.text, "seeds:")?;
+        let seeds: Vec<u64> = line.numbers(seed_list)?;
+        if seeds.is_empty() {
+            return Err(line.error_at_end("at least one seed"));
+        }
+
+        let mut seed_maps: Vec<SeedMapSection> = Vec::new();
+        for line in lines {
+            if line.text.is_empty() {
+                continue;
+            }
+            if let Some(name) = line.text.strip_suffix(" map:") {
+                let (from, to) = line.split_once(name, "-to-")?;
+                seed_maps.push(SeedMapSection {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    map_values: Vec::new(),
+                });
+            } else {
+                let seed_map = seed_maps
+                    .last_mut()
+                    .ok_or_else(|| line.error(1, "a `<from>-to-<to> map:` header"))?;
+
+                let numbers = line.numbers::<u64>(line.text)?;
+                let [dest, src, len] = numbers[..] else {
+                    return Err(line
+                        .error(1, "a destination, source and length")
+                        .found(line.text));
+                };
+                seed_map.add(dest, src, len).map_err(|_| {
+                    let len_token = line.text.split_whitespace().last().unwrap_or(line.text);
+                    line.error_at(len_token, "a length that ends before u64::MAX")
+                })?;
+            }
+        }
+
+        let graph = CategoryGraph::new(&seed_maps);
+        Ok(Almanac {
+            seeds,
+            seed_maps,
+            graph,
+        })
+    }
+}
+
+impl Solution for Almanac {
+    const DAY: u8 = 5;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let almanac: Almanac = input.parse()?;
+        // Both parts need to get from seeds to locations, so a broken chain is bad input
+        if let Err(err) = almanac.location_map() {
+            let mut error = ParseError::new(Self::DAY, 1, 1, "maps from `seed` to `location`");
+            error.found = Some(err.to_string());
+            return Err(error);
+        }
+        Ok(almanac)
+    }
+
+    fn part_one(&self) -> Result<Answer, PartError> {
+        Ok(part1::main(self)?.into())
+    }
+
+    fn part_two(&self) -> Result<Answer, PartError> {
+        Ok(part2::main(self)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_core::{PartError, Solution};
+
+    use crate::{part1, part2, Almanac, ConversionError, RangeOverflow, SeedError};
+
+    #[test]
+    fn parse_errors() {
+        let err = "seeds 79 14".parse::<Almanac>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 5, line 1, column 1: expected `seeds:`, found `seeds 79 14`"
+        );
+
+        let err = "seeds: 79 14\n\n50 98 2".parse::<Almanac>().unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+
+        let err = "seeds: 79 14\n\nseed-to-soil map:\n50 98"
+            .parse::<Almanac>()
+            .unwrap_err();
+        assert_eq!(err.expected, "a destination, source and length");
+
+        let err = "seeds: 79 14\n\nseed-soil map:\n50 98 2"
+            .parse::<Almanac>()
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (3, 10));
+
+        let err = <Almanac as Solution>::parse("seeds: 1\n\nseed-to-soil map:\n0 0 1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 5, line 1, column 1: expected maps from `seed` to `location`, \
+             found no map mentions the `location` category"
+        );
+
+        // Ranges that run past the top would wrap round and give a bogus location
+        let err = format!("seeds: 1\n\nseed-to-soil map:\n5 {} 2", u64::MAX - 1)
+            .parse::<Almanac>()
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (4, 24));
+        assert_eq!(err.expected, "a length that ends before u64::MAX");
+
+        let err = format!("seeds: 1\n\nseed-to-soil map:\n{} 5 2", u64::MAX)
+            .parse::<Almanac>()
+            .unwrap_err();
+        assert_eq!(err.line, 4);
+
+        // Right up against the top is fine
+        let almanac = format!("seeds: 1\n\nseed-to-soil map:\n5 {} 2", u64::MAX - 2)
+            .parse::<Almanac>()
+            .unwrap();
+        assert_eq!(almanac.seed_maps[0].get_seed(u64::MAX - 1), 6);
+
+        let mut section = almanac.seed_maps[0].clone();
+        assert_eq!(section.add(0, u64::MAX, 2), Err(RangeOverflow));
+        assert_eq!(section.add(u64::MAX, 0, 2), Err(RangeOverflow));
+        assert_eq!(section.map_values.len(), 1);
+
+        let err = "seeds:\n\nseed-to-soil map:\n0 0 1"
+            .parse::<Almanac>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 5, line 1, column 7: expected at least one seed, found the end of the line"
+        );
+    }
+
+    #[test]
+    fn no_location_maps() {
+        // Plain parsing doesn't check the chain, the parts find out instead
+        let almanac = "seeds: 1 2\n\nseed-to-soil map:\n0 0 1"
+            .parse::<Almanac>()
+            .unwrap();
+        let err = SeedError::Conversion(ConversionError::UnknownCategory("location".into()));
+        assert_eq!(part1::main(&almanac), Err(err.clone()));
+        assert_eq!(part2::main(&almanac), Err(err));
+    }
+
+    #[test]
+    fn empty_seed_ranges() {
+        // Empty ranges plant nothing, the rest still count
+        let almanac =
+            <Almanac as Solution>::parse("seeds: 5 0 52 2\n\nseed-to-location map:\n0 50 10")
+                .unwrap();
+        assert_eq!(almanac.part_two(), Ok(2u64.into()));
+
+        let almanac =
+            <Almanac as Solution>::parse("seeds: 5 0\n\nseed-to-location map:\n0 50 10").unwrap();
+        // Part one still plants seeds 5 and 0
+        assert_eq!(almanac.part_one(), Ok(0u64.into()));
+        assert_eq!(
+            almanac.part_two(),
+            Err(PartError("there are no seeds to plant".into()))
+        );
+    }
+
+    #[test]
+    fn seeds_past_max() {
+        // Only part two reads the seeds as ranges, so only part two has a problem with this
+        let almanac = format!(
+            "seeds: 1 2 {} 9\n\nseed-to-location map:\n0 50 10",
+            u64::MAX - 3
+        );
+        let almanac = <Almanac as Solution>::parse(&almanac).unwrap();
+        assert_eq!(almanac.part_one(), Ok(1u64.into()));
+        assert_eq!(almanac.seed_pairs(), Err(SeedError::PastMax(2)));
+        assert_eq!(
+            almanac.part_two(),
+            Err(PartError("seed range 2 runs past u64::MAX".into()))
+        );
+    }
+
+    #[test]
+    fn unpaired_seeds() {
+        // A single seed is fine for part one, part two has no length to go with it
+        let almanac =
+            <Almanac as Solution>::parse("seeds: 79 14 55\n\nseed-to-location map:\n0 50 10")
+                .unwrap();
+        assert_eq!(almanac.part_one(), Ok(5u64.into()));
+        assert_eq!(almanac.seed_ranges(), Err(SeedError::Unpaired));
+        assert_eq!(
+            almanac.part_two(),
+            Err(PartError(
+                "the seeds don't pair up into (start, length) ranges".into()
+            ))
+        );
+    }
+}
@@ -1,11 +1,108 @@
-pub fn main() -> u32 {
-    // Here is your daily reminder to save your work
-    // Or make commits
-    // Or just anything to not lose your work
-    // I lost my work
-    // I'm not happy
-    // I'm not happy at all
-    // I'm fucking pissed some might say
-    // Imagine the right answer is here
-    0
+use rayon::prelude::*;
+
+use crate::{bounded, Almanac, SeedError, SeedRanges};
+
+pub fn main(almanac: &Almanac) -> Result<u64, SeedError> {
+    // The seed line is really (start, length) pairs, far too many seeds to walk one by one.
+    // Push each pair through the maps as a whole range instead.
+    let location_map = almanac.location_map()?;
+    almanac
+        .seed_pairs()?
+        .into_par_iter()
+        // An empty range has no locations, so it just drops out
+        .filter_map(|range| location_map.map_range(range).boundaries().first().copied())
+        .min()
+        .ok_or(SeedError::NoSeeds)
+}
+
+// The other way round, for checking `main`: go up through the locations a run at a time until
+// one comes from a seed we actually planted. The runs split wherever a piece of the seed to
+// location map starts or stops landing, so within one run every location comes from the same
+// pieces.
+pub fn search(almanac: &Almanac) -> Option<u64> {
+    let location_map = almanac.location_map().ok()?;
+    let seeds = almanac.seed_ranges().ok()?;
+    if seeds.is_empty() {
+        return None;
+    }
+
+    let domain_end = u64::MAX as u128 + 1;
+    let pieces = location_map.pieces();
+    let mut cuts = pieces
+        .iter()
+        .enumerate()
+        .flat_map(|(index, piece)| {
+            let end = pieces
+                .get(index + 1)
+                .map_or(domain_end, |next| next.start as u128);
+            let shift = |value: u128| (value as i128 + piece.offset) as u128;
+            [shift(piece.start as u128), shift(end)]
+        })
+        .collect::<Vec<_>>();
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    cuts.windows(2).find_map(|run| {
+        let locations = if run[1] >= domain_end {
+            SeedRanges::from(run[0] as u64..)
+        } else {
+            SeedRanges::from(run[0] as u64..run[1] as u64)
+        };
+        let planted = location_map.preimage_ranges(&locations) & &seeds;
+        // Every seed left lands in this run, so the lowest of them is the answer
+        bounded(&planted)
+            .filter_map(|range| location_map.map_range(range).boundaries().first().copied())
+            .min()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Almanac, SeedRanges};
+
+    #[test]
+    fn test() {
+        let almanac = include_str!("../test_input.txt")
+            .parse::<Almanac>()
+            .unwrap();
+        assert_eq!(super::main(&almanac), Ok(46));
+        assert_eq!(super::search(&almanac), Some(46));
+    }
+
+    #[test]
+    fn search_high_up() {
+        // Far too many locations below the answer to try one at a time
+        let start = u64::MAX - 10;
+        let almanac =
+            format!("seeds: {start} 5 20 3\n\nseed-to-location map:\n0 20 3\n5 {start} 5")
+                .parse::<Almanac>()
+                .unwrap();
+        assert_eq!(super::search(&almanac), Some(0));
+        assert_eq!(super::main(&almanac), Ok(0));
+
+        let almanac = format!("seeds: {start} 5\n\nseed-to-location map:\n0 0 1")
+            .parse::<Almanac>()
+            .unwrap();
+        assert_eq!(super::search(&almanac), Some(start));
+        assert_eq!(super::main(&almanac), Ok(start));
+    }
+
+    #[test]
+    fn splits_at_boundaries() {
+        let almanac = include_str!("../test_input.txt")
+            .parse::<Almanac>()
+            .unwrap();
+        let seed_to_soil = &almanac.seed_maps[0];
+
+        // 45..55 is half unmapped and half inside `52 50 48`, 97..100 straddles `50 98 2`
+        let seeds = SeedRanges::from(45..55) | SeedRanges::from(97..101);
+        let soil = seed_to_soil.map_ranges(&seeds);
+
+        let expected = SeedRanges::from(45..50)
+            | SeedRanges::from(52..57)
+            | SeedRanges::from(99..100)
+            | SeedRanges::from(50..52)
+            | SeedRanges::from(100..101);
+        assert_eq!(soil, expected);
+    }
 }
@@ -0,0 +1,316 @@
+use std::ops::Range;
+
+use smallvec::SmallVec;
+
+use crate::{SeedMapSection, SeedRanges};
+
+// One past the biggest value a map can see
+const DOMAIN_END: u128 = u64::MAX as u128 + 1;
+
+// A run of inputs that all get shifted by the same amount.
+// It lasts until the next piece starts (or the end of the domain).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Piece {
+    pub start: u64,
+    pub offset: i128,
+}
+
+/// A map from `0..=u64::MAX` onto itself made of shifted runs, sorted by where they start.
+/// The first piece always starts at 0 so every input lands in exactly one piece.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    pieces: Vec<Piece>,
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> PiecewiseMap {
+        PiecewiseMap {
+            pieces: vec![Piece {
+                start: 0,
+                offset: 0,
+            }],
+        }
+    }
+
+    pub fn from_section(section: &SeedMapSection) -> PiecewiseMap {
+        // Earlier mappings win where they overlap, same as `SeedMapSection::get_seed`
+        let mut covered = SeedRanges::empty();
+        let mut pieces = Vec::new();
+        for &(src, src_max, dest) in &section.map_values {
+            let window = SeedRanges::from(src..src_max);
+            pieces.extend(starts(&(&window - &covered)).map(|start| Piece {
+                start,
+                offset: dest as i128 - src as i128,
+            }));
+            covered |= window;
+        }
+
+        // Anything the section doesn't mention maps to itself
+        pieces.extend(starts(&!covered).map(|start| Piece { start, offset: 0 }));
+
+        pieces.sort_unstable_by_key(|piece| piece.start);
+        PiecewiseMap::from_pieces(pieces)
+    }
+
+    // Glues together neighbours that shift by the same amount
+    fn from_pieces(pieces: Vec<Piece>) -> PiecewiseMap {
+        let mut merged: Vec<Piece> = Vec::with_capacity(pieces.len());
+        for piece in pieces {
+            match merged.last() {
+                Some(last) if last.offset == piece.offset => {}
+                _ => merged.push(piece),
+            }
+        }
+        PiecewiseMap { pieces: merged }
+    }
+
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    fn end(&self, index: usize) -> u128 {
+        self.pieces
+            .get(index + 1)
+            .map_or(DOMAIN_END, |piece| piece.start as u128)
+    }
+
+    fn index_of(&self, value: u64) -> usize {
+        self.pieces.partition_point(|piece| piece.start <= value) - 1
+    }
+
+    pub fn get(&self, value: u64) -> u64 {
+        let piece = self.pieces[self.index_of(value)];
+        (value as i128 + piece.offset) as u64
+    }
+
+    /// `self` then `then`, as a single map.
+    pub fn compose(&self, then: &PiecewiseMap) -> PiecewiseMap {
+        let mut pieces = Vec::new();
+        for (index, piece) in self.pieces.iter().enumerate() {
+            let end = self.end(index) as i128;
+            let mut start = piece.start as i128;
+            // Walk the image of this piece, cutting wherever `then` changes pieces
+            while start < end {
+                let image = start + piece.offset;
+                let then_index = then.index_of(image as u64);
+                let then_end = then.end(then_index) as i128;
+                pieces.push(Piece {
+                    start: start as u64,
+                    offset: piece.offset + then.pieces[then_index].offset,
+                });
+                start = end.min(start + (then_end - image));
+            }
+        }
+        PiecewiseMap::from_pieces(pieces)
+    }
+
+    /// The map run backwards, if it's one-to-one.
+    /// That means the shifted pieces have to tile the whole domain with no gaps or overlaps.
+    pub fn inverse(&self) -> Option<PiecewiseMap> {
+        let mut images = self
+            .pieces
+            .iter()
+            .enumerate()
+            .map(|(index, piece)| {
+                let start = piece.start as i128 + piece.offset;
+                let end = self.end(index) as i128 + piece.offset;
+                (start, end, -piece.offset)
+            })
+            .collect::<Vec<_>>();
+        images.sort_unstable();
+
+        let mut expected_start = 0;
+        for &(start, end, _) in &images {
+            if start != expected_start {
+                return None;
+            }
+            expected_start = end;
+        }
+        if expected_start != DOMAIN_END as i128 {
+            return None;
+        }
+
+        let pieces = images
+            .into_iter()
+            .map(|(start, _, offset)| Piece {
+                start: start as u64,
+                offset,
+            })
+            .collect();
+        Some(PiecewiseMap::from_pieces(pieces))
+    }
+
+    /// Every input that maps onto `value`.
+    /// One-to-one maps give exactly one, but a section can squash two runs together or skip a
+    /// value entirely.
+    pub fn preimage(&self, value: u64) -> SmallVec<[u64; 2]> {
+        self.pieces
+            .iter()
+            .enumerate()
+            .filter_map(|(index, piece)| {
+                let source = value as i128 - piece.offset;
+                (source >= piece.start as i128 && source < self.end(index) as i128)
+                    .then_some(source as u64)
+            })
+            .collect()
+    }
+
+    /// Every input that maps into `ranges`.
+    pub fn preimage_ranges(&self, ranges: &SeedRanges) -> SeedRanges {
+        let mut sources = SeedRanges::empty();
+        for (index, piece) in self.pieces.iter().enumerate() {
+            let start = piece.start as i128;
+            let end = self.end(index) as i128;
+            let image = window(start + piece.offset, end + piece.offset);
+            sources |= shift(&(ranges & &image), -piece.offset, end);
+        }
+        sources
+    }
+
+    pub fn map_range(&self, range: Range<u64>) -> SeedRanges {
+        let mut mapped = SeedRanges::empty();
+        if range.is_empty() {
+            return mapped;
+        }
+
+        let mut index = self.index_of(range.start);
+        let mut start = range.start as u128;
+        while start < range.end as u128 {
+            let piece = self.pieces[index];
+            let end = self.end(index).min(range.end as u128);
+            let shift = |value: u128| (value as i128 + piece.offset) as u64;
+            mapped |= SeedRanges::from(shift(start)..shift(end));
+            start = end;
+            index += 1;
+        }
+        mapped
+    }
+}
+
+// `start..end` as a set, where an `end` of `DOMAIN_END` is open-ended
+fn window(start: i128, end: i128) -> SeedRanges {
+    if end >= DOMAIN_END as i128 {
+        SeedRanges::from(start as u64..)
+    } else {
+        SeedRanges::from(start as u64..end as u64)
+    }
+}
+
+// Moves every range in the set by `offset`, closing an open-ended one off at `end`
+fn shift(ranges: &SeedRanges, offset: i128, end: i128) -> SeedRanges {
+    let mut boundaries = ranges
+        .boundaries()
+        .iter()
+        .map(|&boundary| (boundary as i128 + offset) as u64)
+        .collect::<SmallVec<_>>();
+    if boundaries.len() % 2 == 1 && end < DOMAIN_END as i128 {
+        boundaries.push(end as u64);
+    }
+    SeedRanges::new(boundaries).expect("shifting keeps the boundaries sorted")
+}
+
+// Where each range in the set begins, including an open-ended last one
+fn starts(ranges: &SeedRanges) -> impl Iterator<Item = u64> + '_ {
+    ranges.boundaries().iter().step_by(2).copied()
+}
+
+#[cfg(test)]
+mod test {
+    use super::PiecewiseMap;
+    use crate::{Almanac, SeedRanges};
+
+    #[test]
+    fn matches_sections() {
+        let almanac = include_str!("../test_input.txt")
+            .parse::<Almanac>()
+            .unwrap();
+        let location_map = almanac.location_map().unwrap();
+
+        for seed in 0..200 {
+            let expected = almanac
+                .seed_maps
+                .iter()
+                .fold(seed, |seed, map| map.get_seed(seed));
+            assert_eq!(location_map.get(seed), expected, "seed {seed}");
+        }
+        assert_eq!(location_map.get(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn map_range() {
+        let almanac = include_str!("../test_input.txt")
+            .parse::<Almanac>()
+            .unwrap();
+        let location_map = almanac.location_map().unwrap();
+
+        let by_section = almanac
+            .seed_maps
+            .iter()
+            .fold(SeedRanges::from(40..120), |seeds, map| {
+                map.map_ranges(&seeds)
+            });
+        assert_eq!(location_map.map_range(40..120), by_section);
+        assert_eq!(location_map.map_range(7..7), SeedRanges::empty());
+    }
+
+    #[test]
+    fn identity() {
+        let almanac = include_str!("../test_input.txt")
+            .parse::<Almanac>()
+            .unwrap();
+        let soil = PiecewiseMap::from_section(&almanac.seed_maps[0]);
+        assert_eq!(PiecewiseMap::identity().compose(&soil), soil);
+        assert_eq!(soil.compose(&PiecewiseMap::identity()), soil);
+        assert_eq!(soil.pieces().len(), 4);
+    }
+
+    #[test]
+    fn preimage() {
+        let almanac = include_str!("../test_input.txt")
+            .parse::<Almanac>()
+            .unwrap();
+        let location_map = almanac.location_map().unwrap();
+
+        for location in 0..200 {
+            let expected = (0..200)
+                .filter(|&seed| location_map.get(seed) == location)
+                .collect::<Vec<_>>();
+            assert_eq!(location_map.preimage(location).to_vec(), expected);
+        }
+
+        // 0 and 1 both end up at 5 and nothing lands on 0 or 1
+        let squashed: Almanac = "seeds: 1\n\na-to-b map:\n5 0 1\n5 1 1".parse().unwrap();
+        let squashed = PiecewiseMap::from_section(&squashed.seed_maps[0]);
+        assert_eq!(squashed.preimage(5).to_vec(), [0, 1, 5]);
+        assert!(squashed.preimage(0).is_empty());
+    }
+
+    #[test]
+    fn preimage_ranges() {
+        let almanac = include_str!("../test_input.txt")
+            .parse::<Almanac>()
+            .unwrap();
+        let location_map = almanac.location_map().unwrap();
+
+        let locations = SeedRanges::from(40..60) | SeedRanges::from(90..95);
+        let seeds = location_map.preimage_ranges(&locations);
+        for seed in 0..200 {
+            assert_eq!(
+                seeds.contains(&seed),
+                locations.contains(&location_map.get(seed)),
+                "seed {seed}"
+            );
+        }
+
+        // A run that shifts right up against the top of the domain, with the untouched values
+        // above it still mapping to themselves
+        let top: Almanac = format!("seeds: 1\n\na-to-b map:\n{} 0 10", u64::MAX - 10)
+            .parse()
+            .unwrap();
+        let top = PiecewiseMap::from_section(&top.seed_maps[0]);
+        assert_eq!(
+            top.preimage_ranges(&SeedRanges::from(u64::MAX - 2..)),
+            SeedRanges::from(8..10) | SeedRanges::from(u64::MAX - 2..)
+        );
+    }
+}
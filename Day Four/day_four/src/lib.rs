@@ -0,0 +1,148 @@
+use aoc_core::{Answer, Line, ParseError, PartError, Solution};
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ScratchCard {
+    old_score: u32,
+    matches: usize,
+}
+
+pub fn from_str(s: &str) -> Result<ScratchCard, ParseError> {
+    from_line(Line::new(4, 1, s))
+}
+
+pub fn from_line(line: Line) -> Result<ScratchCard, ParseError> {
+    let (id, all_numbers) = line.split_once(line.text, ":")?;
+    let id = line.expect_prefix(id, "Card")?;
+    line.parse::<u32>(id.trim(), "a card number")?;
+
+    let (numbers, winning_numbers) = line.split_once(all_numbers, "|")?;
+
+    let numbers = line.numbers::<u32>(numbers)?;
+
+    let winning_numbers = line.numbers(winning_numbers)?;
+
+    let matches = numbers
+        .iter()
+        .filter(|&x| winning_numbers.contains(x))
+        .count();
+
+    let old_score = if matches == 0 {
+        0
+    } else {
+        u32::pow(2, matches as u32 - 1)
+    };
+
+    Ok(ScratchCard { old_score, matches })
+}
+
+pub struct ScoreGenerator(Vec<ScratchCard>);
+
+impl ScoreGenerator {
+    // Cards are tracked by their index in the pile, so card 1 is index 0
+    fn get_card_number(&self, card_index: usize) -> &ScratchCard {
+        &self.0[card_index]
+    }
+
+    pub fn old_score(&self) -> u32 {
+        self.0.iter().map(|x| x.old_score).sum()
+    }
+
+    pub fn new_score(&self) -> usize {
+        let total = self.0.len();
+        let cards = (0..self.0.len()).collect_vec();
+        self.recurse(cards, total)
+    }
+
+    fn recurse(&self, cards: Vec<usize>, mut total: usize) -> usize {
+        if cards.is_empty() {
+            return total;
+        }
+
+        let new_cards = cards
+            .into_iter()
+            .filter_map(|index| {
+                let matches = self.get_card_number(index).matches;
+                if matches == 0 {
+                    None
+                } else {
+                    Some((index + 1..index + matches + 1).collect_vec())
+                }
+            })
+            .flatten()
+            .collect_vec();
+        total += new_cards.len();
+
+        self.recurse(new_cards, total)
+    }
+}
+
+impl Solution for ScoreGenerator {
+    const DAY: u8 = 4;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = aoc_core::parse::lines(Self::DAY, input).collect_vec();
+        let cards = lines.iter().map(|&line| from_line(line)).try_collect()?;
+        let cards = ScoreGenerator(cards);
+
+        // Winning copies of cards past the end of the table would send new_score out of bounds
+        for (index, (card, line)) in cards.0.iter().zip(&lines).enumerate() {
+            if index + card.matches >= cards.0.len() {
+                let remaining = cards.0.len() - index - 1;
+                return Err(line.error(1, format!("at most {remaining} matches on this card")));
+            }
+        }
+        Ok(cards)
+    }
+
+    fn part_one(&self) -> Result<Answer, PartError> {
+        Ok(self.old_score().into())
+    }
+
+    fn part_two(&self) -> Result<Answer, PartError> {
+        Ok(self.new_score().into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn test() {
+        let card_6 = super::from_str("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11").unwrap();
+        assert_eq!(card_6.old_score, 0);
+        assert_eq!(card_6.matches, 0);
+
+        let card_1 = super::from_str("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
+        assert_eq!(card_1.old_score, 8);
+        assert_eq!(card_1.matches, 4);
+    }
+
+    #[test]
+    fn test_new_score() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        let cards = <super::ScoreGenerator as aoc_core::Solution>::parse(input).unwrap();
+        assert_eq!(cards.old_score(), 13);
+        assert_eq!(cards.new_score(), 30);
+    }
+
+    #[test]
+    fn test_bad_card() {
+        let err = super::from_str("Card 1: 41 48 | 83 8x6").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 4, line 1, column 20: expected a number, found `8x6`"
+        );
+
+        let err = super::from_str("Card 1: 41 48 83 86 17").unwrap_err();
+        assert_eq!(err.column, 23);
+
+        let err = super::from_str("Cart 1: 41 | 83").unwrap_err();
+        assert_eq!(err.expected, "`Card`");
+    }
+}
@@ -0,0 +1,109 @@
+use std::str::FromStr;
+
+use aoc_core::{Answer, ParseError, PartError, Solution};
+use itertools::Itertools;
+
+#[derive(Debug)]
+pub struct HistoryGrid {
+    grid: Vec<Vec<i64>>,
+}
+
+impl FromStr for HistoryGrid {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let grid = aoc_core::parse::lines(9, input)
+            .map(|line| {
+                let row = line.numbers::<i64>(line.text)?;
+                if row.is_empty() {
+                    return Err(line.error_at_end("at least one number"));
+                }
+                Ok(row)
+            })
+            .try_collect()?;
+        Ok(Self { grid })
+    }
+}
+
+impl HistoryGrid {
+    pub fn sum_all_next(&self) -> i64 {
+        self.grid
+            .iter()
+            .map(|row| generate_next_in_sequence(row))
+            .sum()
+    }
+
+    pub fn sum_all_prev(&self) -> i64 {
+        self.grid
+            .iter()
+            .map(|row| generate_next_in_sequence(&row.iter().rev().copied().collect_vec()))
+            .sum()
+    }
+}
+
+fn generate_next_in_sequence(seq: &[i64]) -> i64 {
+    if seq.iter().all(|x| seq.first().unwrap() == x) {
+        *seq.first().unwrap()
+    } else {
+        let diffs = seq.iter().tuple_windows().map(|(a, b)| b - a).collect_vec();
+        let next = generate_next_in_sequence(&diffs);
+        seq.last().unwrap() + next
+    }
+}
+
+impl Solution for HistoryGrid {
+    const DAY: u8 = 9;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, PartError> {
+        Ok(self.sum_all_next().into())
+    }
+
+    fn part_two(&self) -> Result<Answer, PartError> {
+        Ok(self.sum_all_prev().into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn gen_next() {
+        let seq = vec![0, 3, 6];
+        assert_eq!(super::generate_next_in_sequence(&seq), 9);
+
+        let seq = vec![10, 13, 16, 21, 30, 45];
+        assert_eq!(super::generate_next_in_sequence(&seq), 68);
+    }
+
+    #[test]
+    fn gen_prev() {
+        let seq = [0, 3, 6].iter().rev().copied().collect::<Vec<_>>();
+        assert_eq!(super::generate_next_in_sequence(&seq), -3);
+
+        let seq = [10, 13, 16, 21, 30, 45]
+            .iter()
+            .rev()
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(super::generate_next_in_sequence(&seq), 5);
+    }
+
+    #[test]
+    fn parse() {
+        let grid = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45"
+            .parse::<super::HistoryGrid>()
+            .unwrap();
+        assert_eq!(grid.sum_all_next(), 114);
+        assert_eq!(grid.sum_all_prev(), 2);
+
+        let err = "0 3 6\n1 3.5 6".parse::<super::HistoryGrid>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 9, line 2, column 3: expected a number, found `3.5`"
+        );
+        assert!("0 3 6\n\n1 3 6".parse::<super::HistoryGrid>().is_err());
+    }
+}
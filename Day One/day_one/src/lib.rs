@@ -0,0 +1,85 @@
+use aoc_core::{Answer, ParseError, PartError, Solution};
+
+pub fn get_calibration(input: &str) -> Option<i32> {
+    let first_num = input.chars().find(|c| c.is_ascii_digit())?;
+
+    let last_num = input.chars().rev().find(|c| c.is_ascii_digit())?;
+
+    format!("{}{}", first_num, last_num).parse::<i32>().ok()
+}
+
+pub fn get_calibration_sum(input: &str) -> Result<i32, ParseError> {
+    aoc_core::parse::lines(CalibrationDocument::DAY, input)
+        .map(|line| {
+            get_calibration(line.text).ok_or_else(|| line.error_at_end("at least one digit"))
+        })
+        .sum()
+}
+
+// Both parts read the same calibration document, part two just reads the spelt out digits too
+pub struct CalibrationDocument(String);
+
+impl Solution for CalibrationDocument {
+    const DAY: u8 = 1;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        // Part two reads every line part one can and spelt out digits too, so anything it can't
+        // read is no good for either part. Part one checks for plain digits when it runs.
+        day_one_part_two::answer(input)?;
+        Ok(CalibrationDocument(input.to_string()))
+    }
+
+    fn part_one(&self) -> Result<Answer, PartError> {
+        Ok(get_calibration_sum(&self.0)?.into())
+    }
+
+    fn part_two(&self) -> Result<Answer, PartError> {
+        Ok(day_one_part_two::answer(&self.0)?.into())
+    }
+}
+
+mod tests {
+    #[test]
+    fn test() {
+        let line_one = "1abc2";
+
+        assert_eq!(super::get_calibration(line_one), Some(12));
+    }
+
+    #[test]
+    fn test_all() {
+        let input = "1abc2
+        pqr3stu8vwx
+        a1b2c3d4e5f
+        treb7uchet";
+
+        assert_eq!(super::get_calibration_sum(input), Ok(142));
+    }
+
+    #[test]
+    fn test_missing_digit() {
+        let err = super::get_calibration_sum("1abc2\neightwothree").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(
+            err.to_string(),
+            "day 1, line 2, column 13: expected at least one digit, found the end of the line"
+        );
+    }
+
+    #[test]
+    fn spelt_out_only() {
+        use aoc_core::Solution;
+
+        // Fine for part two, part one has nothing to go on
+        let document = super::CalibrationDocument::parse("1abc2\neightwothree").unwrap();
+        assert_eq!(document.part_two(), Ok(95u32.into()));
+        assert_eq!(
+            document.part_one().unwrap_err().to_string(),
+            "day 1, line 2, column 13: expected at least one digit, found the end of the line"
+        );
+        assert!(super::CalibrationDocument::parse("1abc2\nabc").is_err());
+
+        let document = super::CalibrationDocument::parse("1oneé").unwrap();
+        assert_eq!(document.part_two(), Ok(11u32.into()));
+    }
+}
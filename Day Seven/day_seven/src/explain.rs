@@ -0,0 +1,179 @@
+use crate::{Card, Hand, HandState, Rules};
+
+/// What a hand's wild cards stand in for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// The hand with every wild card swapped for the card it counts as
+    pub effective: Vec<Card>,
+    /// The category `effective` makes, the same one the hand itself ranks as
+    pub state: HandState,
+}
+
+impl Hand {
+    /// The best concrete hand this one can be under `rules`. Wild cards all join the biggest
+    /// group, picking the strongest card when groups tie, or become the strongest card there is
+    /// when the whole hand is wild.
+    pub fn explain(&self, rules: &Rules) -> Explanation {
+        let mut counts = [0usize; 13];
+        for &card in self.cards.iter().filter(|&&card| !rules.is_wild(card)) {
+            counts[card as usize] += 1;
+        }
+
+        let stand_in = self
+            .cards
+            .iter()
+            .copied()
+            .filter(|&card| !rules.is_wild(card))
+            .max_by_key(|&card| (counts[card as usize], rules.strength(card)))
+            // Nothing to join, so go for the best card that isn't wild itself
+            .or_else(|| strongest_tame_card(rules));
+
+        let effective = self
+            .cards
+            .iter()
+            .map(|&card| match stand_in {
+                Some(stand_in) if rules.is_wild(card) => stand_in,
+                _ => card,
+            })
+            .collect::<Vec<_>>();
+        Explanation {
+            state: self.state,
+            effective,
+        }
+    }
+}
+
+fn strongest_tame_card(rules: &Rules) -> Option<Card> {
+    Card::ALL
+        .into_iter()
+        .filter(|&card| !rules.is_wild(card))
+        .max_by_key(|&card| rules.strength(card))
+}
+
+/// One bet in a finished game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BetExplanation {
+    pub hand: Hand,
+    pub explanation: Explanation,
+    /// 1 for the weakest hand up to the number of bets for the strongest
+    pub rank: usize,
+    pub bid: u64,
+    /// `bid * rank`, which always fits a `u128`
+    pub winnings: u128,
+}
+
+const HEADERS: [&str; 6] = ["Hand", "Effective", "Type", "Rank", "Bid", "Winnings"];
+
+/// Lays the bets out as a plain text table, one row per bet.
+pub fn table(rows: &[BetExplanation]) -> String {
+    let cells = rows
+        .iter()
+        .map(|row| {
+            vec![
+                row.hand.to_string(),
+                row.explanation
+                    .effective
+                    .iter()
+                    .map(|card| card.to_char())
+                    .collect(),
+                format!("{:?}", row.explanation.state),
+                row.rank.to_string(),
+                row.bid.to_string(),
+                row.winnings.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    aoc_core::report::table(&HEADERS, &cells)
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_core::Line;
+
+    use crate::{Game, Hand, HandState, Rules};
+
+    fn explain(s: &str, rules: &Rules) -> (String, HandState) {
+        let hand = Hand::parse(Line::new(7, 1, s), s, rules, None).unwrap();
+        let explanation = hand.explain(rules);
+        // The effective hand has to make the same category with nothing wild
+        let standard = Rules::standard();
+        assert_eq!(
+            HandState::from_cards(&explanation.effective, &standard),
+            explanation.state
+        );
+        let effective = explanation.effective.iter().map(|card| card.to_char());
+        (effective.collect(), explanation.state)
+    }
+
+    #[test]
+    fn substitutions() {
+        let jokers = Rules::jokers_wild();
+        assert_eq!(
+            explain("T55J5", &jokers),
+            ("T5555".into(), HandState::FourOfAKind)
+        );
+        assert_eq!(
+            explain("KTJJT", &jokers),
+            ("KTTTT".into(), HandState::FourOfAKind)
+        );
+        // Tied groups go to the stronger card
+        assert_eq!(
+            explain("QQKKJ", &jokers),
+            ("QQKKK".into(), HandState::FullHouse)
+        );
+        assert_eq!(
+            explain("2345J", &jokers),
+            ("23455".into(), HandState::OnePair)
+        );
+        assert_eq!(
+            explain("JJJJJ", &jokers),
+            ("AAAAA".into(), HandState::FiveOfAKind)
+        );
+        // Nothing wild, nothing changes
+        assert_eq!(
+            explain("32T3K", &jokers),
+            ("32T3K".into(), HandState::OnePair)
+        );
+        assert_eq!(
+            explain("KTJJT", &Rules::standard()),
+            ("KTJJT".into(), HandState::TwoPair)
+        );
+
+        // Aces are wild and jacks are the best card left
+        let rules = Rules::custom("23456789TQKJA", "A").unwrap();
+        assert_eq!(
+            explain("AAA", &rules),
+            ("JJJ".into(), HandState::ThreeOfAKind)
+        );
+
+        // Groups bigger than a u8 still join the right one
+        let big = "3".repeat(299) + &"2".repeat(300) + "J";
+        let (effective, state) = explain(&big, &jokers);
+        assert_eq!(effective.matches('2').count(), 301);
+        assert_eq!(state, HandState::FiveOfAKind);
+    }
+
+    #[test]
+    fn table() {
+        let game =
+            Game::from_str_with_rules(include_str!("../test_input.txt"), &Rules::jokers_wild())
+                .unwrap();
+        let bets = game.explain();
+        assert_eq!(bets.iter().map(|bet| bet.winnings).sum::<u128>(), 5905);
+        assert_eq!(bets[4].hand.to_string(), "KTJJT");
+        assert_eq!(bets[4].rank, 5);
+
+        let table = super::table(&bets);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(
+            lines[0],
+            " Hand | Effective |        Type | Rank | Bid | Winnings"
+        );
+        assert_eq!(
+            lines[6],
+            "KTJJT |     KTTTT | FourOfAKind |    5 | 220 |     1100"
+        );
+    }
+}
@@ -0,0 +1,774 @@
+use std::str::FromStr;
+
+use aoc_core::{Answer, Line, ParseError, PartError, Solution};
+use itertools::Itertools;
+
+pub mod explain;
+pub mod poker;
+pub mod rules;
+pub mod stats;
+
+pub use explain::Explanation;
+pub use poker::PokerRank;
+pub use rules::Rules;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub enum Card {
+    Ace = 12,
+    King = 11,
+    Queen = 10,
+    Jack = 9,
+    Ten = 8,
+    Nine = 7,
+    Eight = 6,
+    Seven = 5,
+    Six = 4,
+    Five = 3,
+    Four = 2,
+    Three = 1,
+    Two = 0,
+}
+
+impl Card {
+    /// Every card, weakest first in the usual order.
+    pub const ALL: [Card; 13] = [
+        Card::Two,
+        Card::Three,
+        Card::Four,
+        Card::Five,
+        Card::Six,
+        Card::Seven,
+        Card::Eight,
+        Card::Nine,
+        Card::Ten,
+        Card::Jack,
+        Card::Queen,
+        Card::King,
+        Card::Ace,
+    ];
+
+    pub fn from_char(c: char) -> Option<Card> {
+        match c {
+            'A' => Some(Card::Ace),
+            'K' => Some(Card::King),
+            'Q' => Some(Card::Queen),
+            'J' => Some(Card::Jack),
+            'T' => Some(Card::Ten),
+            '9' => Some(Card::Nine),
+            '8' => Some(Card::Eight),
+            '7' => Some(Card::Seven),
+            '6' => Some(Card::Six),
+            '5' => Some(Card::Five),
+            '4' => Some(Card::Four),
+            '3' => Some(Card::Three),
+            '2' => Some(Card::Two),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        b"23456789TJQKA"[self as usize] as char
+    }
+}
+
+/// Only poker looks at suits, Camel Cards hands are usually written without them.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl Suit {
+    pub fn from_char(c: char) -> Option<Suit> {
+        match c {
+            'c' => Some(Suit::Clubs),
+            'd' => Some(Suit::Diamonds),
+            'h' => Some(Suit::Hearts),
+            's' => Some(Suit::Spades),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        b"cdhs"[self as usize] as char
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum HandState {
+    FiveOfAKind = 6,
+    FourOfAKind = 5,
+    FullHouse = 4,
+    ThreeOfAKind = 3,
+    TwoPair = 2,
+    OnePair = 1,
+    HighCard = 0,
+}
+
+impl HandState {
+    pub fn from_cards(cards: &[Card], rules: &Rules) -> HandState {
+        HandState::from_shape(&Shape::from_cards(cards, rules))
+    }
+
+    // Named after the biggest two groups, so it works for any hand size even though the names
+    // come from five card hands. Ranking uses the whole `Shape`.
+    pub fn from_shape(shape: &Shape) -> HandState {
+        let max_count = shape.0.first().copied().unwrap_or_default();
+        let second_max_count = shape.0.get(1).copied().unwrap_or_default();
+        match max_count {
+            5.. => HandState::FiveOfAKind,
+            4 => HandState::FourOfAKind,
+            3 => match second_max_count {
+                2.. => HandState::FullHouse,
+                _ => HandState::ThreeOfAKind,
+            },
+            2 => match second_max_count {
+                2 => HandState::TwoPair,
+                _ => HandState::OnePair,
+            },
+            _ => HandState::HighCard,
+        }
+    }
+}
+
+/// How many of each card a hand has, biggest group first, e.g. a full house is `[3, 2]`.
+/// Shapes compare lexicographically, which ranks five card hands the same way the named
+/// categories do and carries on working for any other hand size.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct Shape(pub Vec<usize>);
+
+impl Shape {
+    pub fn from_cards(cards: &[Card], rules: &Rules) -> Shape {
+        let mut counts = [0usize; 13];
+        let mut wild_card_count = 0;
+        for card in cards {
+            // We don't count wild cards towards any group until we know which is biggest
+            if rules.is_wild(*card) {
+                wild_card_count += 1;
+            } else {
+                counts[*card as usize] += 1;
+            }
+        }
+
+        let mut counts = counts
+            .into_iter()
+            .filter(|&count| count > 0)
+            .collect::<Vec<_>>();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        // Wild cards are always the best card possible, which is always more of whatever there's
+        // already most of (QQKAJ becomes QQKAQ, QQKKJ becomes QQKKQ, JJJJJ stays five of a kind)
+        match counts.first_mut() {
+            Some(max_count) => *max_count += wild_card_count,
+            None if wild_card_count > 0 => counts.push(wild_card_count),
+            None => {}
+        }
+
+        Shape(counts)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Hand {
+    pub cards: Vec<Card>,
+    // One per card when the hand was written with suits, like `Ah2h3d`
+    pub suits: Option<Vec<Suit>>,
+    pub shape: Shape,
+    pub state: HandState,
+    // Each card's rank under the rules the hand was dealt with, for breaking ties
+    strengths: Vec<u8>,
+    key: Option<u32>,
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.shape
+            .cmp(&other.shape)
+            .then_with(|| self.strengths.cmp(&other.strengths))
+    }
+}
+
+// Equal whenever they rank the same, suits don't count for Camel Cards
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Hand {}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for Hand {
+    // Written back the way it was parsed
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, card) in self.cards.iter().enumerate() {
+            write!(f, "{}", card.to_char())?;
+            if let Some(suits) = &self.suits {
+                write!(f, "{}", suits[index].to_char())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Hand {
+    pub fn new(cards: Vec<Card>, rules: &Rules) -> Hand {
+        let shape = Shape::from_cards(&cards, rules);
+        let state = HandState::from_shape(&shape);
+        let strengths = cards
+            .iter()
+            .map(|&card| rules.strength(card))
+            .collect::<Vec<_>>();
+        // Up to five cards every shape has its own category, so the category can stand in for it
+        let key = (cards.len() <= 5).then(|| {
+            strengths
+                .iter()
+                .chain(std::iter::repeat(&0))
+                .take(5)
+                .fold(state as u32, |key, &strength| key << 4 | strength as u32)
+        });
+        Hand {
+            state,
+            shape,
+            strengths,
+            key,
+            cards,
+            suits: None,
+        }
+    }
+
+    /// The hand packed into a single number, category in the top bits then four bits per card,
+    /// that orders the same way as the hand does against others of its size. Only hands of up to
+    /// five cards fit.
+    pub fn sort_key(&self) -> Option<u32> {
+        self.key
+    }
+
+    // Parses `s`, a slice of `line`, as a hand of cards, exactly `size` of them once the game
+    // has settled on a size
+    fn parse<'a>(
+        line: Line<'a>,
+        s: &'a str,
+        rules: &Rules,
+        size: Option<usize>,
+    ) -> Result<Hand, ParseError> {
+        let mut cards = Vec::with_capacity(size.unwrap_or(5));
+        let mut suits = Vec::new();
+        let mut chars = s.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            if size == Some(cards.len()) {
+                return Err(line.error_at(&s[index..], format!("a hand of {} cards", cards.len())));
+            }
+            let card = Card::from_char(c)
+                .ok_or_else(|| line.error_at(&s[index..index + c.len_utf8()], "a card"))?;
+            cards.push(card);
+
+            // Either every card has a suit after it or none do, the first card decides which
+            let suit = chars.next_if(|&(_, c)| Suit::from_char(c).is_some());
+            let end = suit.map_or(index + c.len_utf8(), |(suit_index, _)| suit_index + 1);
+            match suit {
+                Some((_, suit)) if cards.len() == suits.len() + 1 => {
+                    suits.push(Suit::from_char(suit).unwrap())
+                }
+                None if suits.is_empty() => {}
+                Some(_) => return Err(line.error_at(&s[index..end], "a card without a suit")),
+                None => return Err(line.error_at(&s[index..end], "a card with a suit")),
+            }
+        }
+
+        match size {
+            Some(size) if cards.len() < size => {
+                Err(line.error_at(s, format!("a hand of {size} cards")))
+            }
+            None if cards.is_empty() => Err(line.error_at(s, "a hand of cards")),
+            _ => {
+                let mut hand = Hand::new(cards, rules);
+                hand.suits = (!suits.is_empty()).then_some(suits);
+                Ok(hand)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Bet {
+    amount: u64,
+    hand: Hand,
+    // Which line of the input it came from, equal hands keep this order
+    line: usize,
+}
+
+impl Ord for Bet {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.hand.cmp(&other.hand)
+    }
+}
+
+impl PartialOrd for Bet {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Bet {
+    fn from_line(line: Line, rules: &Rules, hand_size: Option<usize>) -> Result<Bet, ParseError> {
+        // Any amount of whitespace around and between the hand and the bid
+        let mut tokens = line.text.split_whitespace();
+        let hand = tokens
+            .next()
+            .ok_or_else(|| line.error_at_end("a hand of cards"))?;
+        let hand = Hand::parse(line, hand, rules, hand_size)?;
+        let amount = tokens.next().ok_or_else(|| line.error_at_end("a bid"))?;
+        let amount = line.parse::<u64>(amount, "a bid")?;
+        if let Some(extra) = tokens.next() {
+            return Err(line.error_at(extra, "the end of the line"));
+        }
+        Ok(Bet {
+            amount,
+            hand,
+            line: line.number,
+        })
+    }
+}
+
+/// How a game ranks its hands.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Scoring {
+    /// By shape, then card by card in the order they were dealt.
+    #[default]
+    CamelCards,
+    /// Real poker hands with straights, flushes and kickers, longer hands scoring as their best
+    /// five cards. See `poker::rank`.
+    Poker,
+}
+
+#[derive(Debug)]
+pub struct Game {
+    // Weakest first under `scoring`
+    bets: Vec<Bet>,
+    rules: Rules,
+    scoring: Scoring,
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Game, ParseError> {
+        Game::from_str_with_rules(s, &Rules::standard())
+    }
+}
+
+impl Game {
+    pub fn from_str_with_rules(s: &str, rules: &Rules) -> Result<Game, ParseError> {
+        // Hands can be any size, but the first one decides it for the whole game
+        let mut hand_size = None;
+        let mut bets: Vec<Bet> = aoc_core::parse::lines(7, s)
+            .filter(|line| !line.text.trim().is_empty())
+            .map(|line| {
+                let bet = Bet::from_line(line, rules, hand_size)?;
+                hand_size = Some(bet.hand.cards.len());
+                Ok::<_, ParseError>(bet)
+            })
+            .try_collect()?;
+        sort_bets(&mut bets);
+        Ok(Game {
+            bets,
+            rules: rules.clone(),
+            scoring: Scoring::CamelCards,
+        })
+    }
+
+    /// The same bets ranked under `scoring` instead. Hands that tie under poker rules keep their
+    /// Camel Cards order.
+    pub fn scored_by(mut self, scoring: Scoring) -> Game {
+        sort_bets(&mut self.bets);
+        if scoring == Scoring::Poker {
+            self.bets
+                .sort_by_cached_key(|bet| poker::rank(&bet.hand, &self.rules));
+        }
+        self.scoring = scoring;
+        self
+    }
+
+    pub fn scoring(&self) -> Scoring {
+        self.scoring
+    }
+
+    /// Every bet weakest first, with what its wild cards became and what it won.
+    pub fn explain(&self) -> Vec<explain::BetExplanation> {
+        self.bets
+            .iter()
+            .enumerate()
+            .map(|(index, bet)| explain::BetExplanation {
+                hand: bet.hand.clone(),
+                explanation: bet.hand.explain(&self.rules),
+                rank: index + 1,
+                bid: bet.amount,
+                winnings: bet.amount as u128 * (index as u128 + 1),
+            })
+            .collect()
+    }
+
+    /// Every bid times its rank, added up, unless that runs past `u64::MAX`.
+    pub fn winnings(&self) -> Result<u64, WinningsOverflow> {
+        self.bets
+            .iter()
+            .enumerate()
+            .try_fold(0u64, |total, (index, bet)| {
+                total.checked_add(bet.winnings(index)?)
+            })
+            .ok_or(WinningsOverflow)
+    }
+}
+
+impl Bet {
+    // What the bet wins at `index` in the sorted bets, if it fits
+    fn winnings(&self, index: usize) -> Option<u64> {
+        self.amount.checked_mul(index as u64 + 1)
+    }
+}
+
+/// Winnings too big for a `u64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WinningsOverflow;
+
+impl std::fmt::Display for WinningsOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "winnings run past u64::MAX")
+    }
+}
+
+impl std::error::Error for WinningsOverflow {}
+
+// Past this many bets radix sorting the keys beats comparing them
+const RADIX_SORT_MIN: usize = 1 << 12;
+
+// Sorts weakest hand first, keeping bets on equal hands in the order they came in. Every hand in
+// a game is the same size, so either they all have keys or none do.
+fn sort_bets(bets: &mut Vec<Bet>) {
+    if !bets.iter().all(|bet| bet.hand.key.is_some()) {
+        bets.sort();
+    } else if bets.len() >= RADIX_SORT_MIN {
+        radix_sort(bets);
+    } else {
+        bets.sort_by_key(|bet| bet.hand.key);
+    }
+}
+
+// A stable least significant digit radix sort on the hands' keys, a byte at a time
+fn radix_sort(bets: &mut Vec<Bet>) {
+    let mut order = bets
+        .iter()
+        .enumerate()
+        .map(|(index, bet)| (bet.hand.key.expect("every hand has a key"), index))
+        .collect::<Vec<_>>();
+    let mut scratch = order.clone();
+    for shift in (0..u32::BITS).step_by(8) {
+        let digit = |key: u32| (key >> shift & 0xff) as usize;
+        let mut counts = [0; 256];
+        for &(key, _) in &order {
+            counts[digit(key)] += 1;
+        }
+        // Every key has the same byte here, so nothing would move
+        if counts.contains(&order.len()) {
+            continue;
+        }
+
+        // Turn the counts into where each digit's run starts
+        let mut total = 0;
+        for count in &mut counts {
+            (*count, total) = (total, total + *count);
+        }
+        for &(key, index) in &order {
+            scratch[counts[digit(key)]] = (key, index);
+            counts[digit(key)] += 1;
+        }
+        std::mem::swap(&mut order, &mut scratch);
+    }
+
+    let mut unsorted = std::mem::take(bets)
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+    bets.extend(
+        order
+            .into_iter()
+            .map(|(_, index)| unsorted[index].take().expect("each bet is used once")),
+    );
+}
+
+// Camel Cards scored both ways, without jokers for part one and with J as a joker for part two
+pub struct CamelCards {
+    standard: Game,
+    jokers: Game,
+}
+
+impl Solution for CamelCards {
+    const DAY: u8 = 7;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(CamelCards {
+            standard: Game::from_str_with_rules(input, &Rules::standard())?,
+            jokers: Game::from_str_with_rules(input, &Rules::jokers_wild())?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, PartError> {
+        Ok(self.standard.winnings()?.into())
+    }
+
+    fn part_two(&self) -> Result<Answer, PartError> {
+        Ok(self.jokers.winnings()?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_core::{Line, Solution};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::str::FromStr;
+
+    use crate::{Card, HandState, Rules};
+
+    fn parse_hand(s: &str, rules: &Rules) -> super::Hand {
+        super::Hand::parse(Line::new(7, 1, s), s, rules, None).unwrap()
+    }
+
+    #[test]
+    fn test_full_parse() {
+        // The real puzzle input isn't checked in, so this only runs where it's been added
+        let Some(input) = aoc_core::input::workspace_input(7) else {
+            return;
+        };
+        let game = super::Game::from_str(&input).unwrap();
+        assert_eq!(game.bets.len(), 1000);
+        let game = super::Game::from_str_with_rules(&input, &Rules::jokers_wild()).unwrap();
+        assert_eq!(game.bets.len(), 1000);
+    }
+
+    #[test]
+    fn test() {
+        let input = include_str!("../test_input.txt");
+        let game = super::Game::from_str(input).unwrap();
+        assert_eq!(game.winnings().unwrap(), 6440);
+    }
+
+    #[test]
+    fn test_jokers_wild() {
+        let input = include_str!("../test_input.txt");
+        let game = super::Game::from_str_with_rules(input, &Rules::jokers_wild()).unwrap();
+        assert_eq!(game.winnings().unwrap(), 5905);
+    }
+
+    #[test]
+    fn test_wild_card() {
+        let jokers = Rules::jokers_wild();
+        assert_eq!(parse_hand("32T3K", &jokers).state, HandState::OnePair);
+        assert_eq!(parse_hand("T55J5", &jokers).state, HandState::FourOfAKind);
+        assert_eq!(parse_hand("KK677", &jokers).state, HandState::TwoPair);
+        assert_eq!(parse_hand("KTJJT", &jokers).state, HandState::FourOfAKind);
+        assert_eq!(parse_hand("QQQJA", &jokers).state, HandState::FourOfAKind);
+        assert_eq!(parse_hand("JJJJJ", &jokers).state, HandState::FiveOfAKind);
+        assert_eq!(parse_hand("QQKKJ", &jokers).state, HandState::FullHouse);
+        assert_eq!(parse_hand("2345J", &jokers).state, HandState::OnePair);
+
+        // Under the standard rules J is just a jack
+        let standard = Rules::standard();
+        assert_eq!(parse_hand("KTJJT", &standard).state, HandState::TwoPair);
+
+        // Jokers lose ties, jacks don't
+        assert!(parse_hand("JKKK2", &jokers) < parse_hand("QQQQ2", &jokers));
+        assert!(parse_hand("JKKK3", &standard) > parse_hand("2KKK3", &standard));
+    }
+
+    #[test]
+    fn test_other_sizes() {
+        let standard = Rules::standard();
+        let shape = |s: &str| parse_hand(s, &standard).shape.0;
+        assert_eq!(shape("KKK"), [3]);
+        assert_eq!(shape("K2K"), [2, 1]);
+        assert_eq!(shape("AAKKK22"), [3, 2, 2]);
+        assert_eq!(shape("AAKKKK2"), [4, 2, 1]);
+        assert_eq!(shape("JJJ"), [3]);
+        assert_eq!(
+            shape("JJJ"),
+            parse_hand("JJJ", &Rules::jokers_wild()).shape.0
+        );
+
+        // Three of a kind and a pair beats three of a kind and two singles in seven cards too
+        assert!(parse_hand("AAKKK23", &standard) < parse_hand("22KKK33", &standard));
+        assert!(parse_hand("22KKK33", &standard) < parse_hand("2222345", &standard));
+
+        // Far more of a card than a u8 could count
+        let big = "K".repeat(300) + "JJ";
+        assert_eq!(shape(&big), [300, 2]);
+        assert_eq!(parse_hand(&big, &Rules::jokers_wild()).shape.0, [302]);
+
+        let game = super::Game::from_str("KK2 1\n23Q 10\nAAA 100").unwrap();
+        assert_eq!(game.winnings().unwrap(), 10 + 2 + 300);
+
+        let game = super::Game::from_str_with_rules(
+            "JJ23456 5\n2345678 7\n2233445 11",
+            &Rules::jokers_wild(),
+        )
+        .unwrap();
+        // 2345678 high card, 2233445 three pair, JJ23456 three of a kind
+        assert_eq!(game.winnings().unwrap(), 7 + 11 * 2 + 5 * 3);
+    }
+
+    // A big game of made up hands, the same every time
+    fn random_game(bets: usize, hand_size: usize) -> String {
+        let mut rng = StdRng::seed_from_u64(7);
+        (0..bets)
+            .map(|_| {
+                let hand = (0..hand_size)
+                    .map(|_| Card::ALL[rng.gen_range(0..Card::ALL.len())].to_char())
+                    .collect::<String>();
+                format!("{hand} {}\n", rng.gen_range(1..=1000))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_keys() {
+        let input = random_game(20_000, 5);
+        for rules in [Rules::standard(), Rules::jokers_wild()] {
+            let game = super::Game::from_str_with_rules(&input, &rules).unwrap();
+            let mut by_comparison = game.bets.clone();
+            by_comparison.sort();
+            assert_eq!(game.bets, by_comparison);
+
+            // Starting from a different order, so ties come out differently but still agree
+            let mut reversed = by_comparison.clone();
+            reversed.reverse();
+            let mut by_key = reversed.clone();
+            by_key.sort_by_key(|bet| bet.hand.sort_key());
+            let mut by_radix = reversed.clone();
+            super::radix_sort(&mut by_radix);
+            reversed.sort();
+            assert_eq!(by_key, reversed);
+            assert_eq!(by_radix, reversed);
+
+            for pair in game.bets.windows(2) {
+                assert_eq!(
+                    pair[0].hand.sort_key().cmp(&pair[1].hand.sort_key()),
+                    pair[0].hand.cmp(&pair[1].hand)
+                );
+            }
+        }
+
+        // Shorter hands get keys too, longer ones fall back to comparing
+        let standard = Rules::standard();
+        assert!(parse_hand("KK2", &standard).sort_key() < parse_hand("222", &standard).sort_key());
+        assert_eq!(parse_hand("AAKKK22", &standard).sort_key(), None);
+        let game = super::Game::from_str(&random_game(5_000, 7)).unwrap();
+        assert!(game.bets.is_sorted());
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        // Twos are wild and aces count low
+        let rules = Rules::custom("A23456789TJQK", "2").unwrap();
+        assert_eq!(parse_hand("22KQA", &rules).state, HandState::ThreeOfAKind);
+        assert!(parse_hand("A3456", &rules) < parse_hand("33456", &rules));
+    }
+
+    #[test]
+    fn test_loose_input() {
+        let game = super::Game::from_str(
+            "  32T3K\t765\n\nT55J5   684  \nKK677 28\nKTJJT 220\nQQQJA 483\n",
+        )
+        .unwrap();
+        assert_eq!(game.winnings().unwrap(), 6440);
+
+        // Bids and winnings past u32::MAX
+        let game = super::Game::from_str("22222 5000000000\n33333 4000000000").unwrap();
+        assert_eq!(game.winnings().unwrap(), 5_000_000_000 + 4_000_000_000 * 2);
+    }
+
+    #[test]
+    fn test_winnings_overflow() {
+        let input = "22222 18446744073709551615\n33333 18446744073709551615";
+        let game = super::Game::from_str(input).unwrap();
+        assert_eq!(game.winnings(), Err(super::WinningsOverflow));
+        assert_eq!(game.winnings_by_category(), Err(super::WinningsOverflow));
+        // Explaining still works, every bet's winnings fit on their own
+        assert_eq!(game.explain()[1].winnings, u64::MAX as u128 * 2);
+
+        let cards = super::CamelCards::parse(input).unwrap();
+        assert_eq!(
+            cards.part_one().unwrap_err().to_string(),
+            "winnings run past u64::MAX"
+        );
+        assert!(cards.part_two().is_err());
+    }
+
+    #[test]
+    fn test_bad_bet() {
+        let err = super::Game::from_str("32T3K 765\nT55x5 684").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 7, line 2, column 4: expected a card, found `x`"
+        );
+
+        let err = super::Game::from_str("32T3K 765\n32T3KK 765").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.expected, "a hand of 5 cards");
+
+        let err = super::Game::from_str("32T3K 765\n32T 765").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 7, line 2, column 1: expected a hand of 5 cards, found `32T`"
+        );
+
+        // A lone bid reads as a three card hand with nothing after it
+        let err = super::Game::from_str(" 765").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (5, "a bid"));
+
+        let err = super::Game::from_str("32T3k 765").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 7, line 1, column 5: expected a card, found `k`"
+        );
+
+        let err = super::Game::from_str("32T3K 765 12").unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (11, "the end of the line")
+        );
+
+        let err = super::Game::from_str("32T3K lots").unwrap_err();
+        assert_eq!(err.expected, "a bid");
+
+        // Suits are all or nothing
+        let err = super::Game::from_str("3h2dT3s3cKd 765").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 7, line 1, column 5: expected a card with a suit, found `T`"
+        );
+        let err = super::Game::from_str("32dT3K 765").unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (2, "a card without a suit")
+        );
+        assert!(super::Game::from_str("3h2dTs3cKd 765").is_ok());
+    }
+
+    #[test]
+    fn test_equality_matches_order() {
+        let standard = Rules::standard();
+        // Only poker looks at suits, so they rank the same and have to be equal too
+        let plain = parse_hand("32T3K", &standard);
+        let suited = parse_hand("3h2dTs3cKd", &standard);
+        assert_eq!(plain.cmp(&suited), std::cmp::Ordering::Equal);
+        assert_eq!(plain, suited);
+        assert_ne!(plain, parse_hand("32T3A", &standard));
+    }
+}
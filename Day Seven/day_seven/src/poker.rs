@@ -0,0 +1,246 @@
+use itertools::Itertools;
+
+use crate::{Card, Hand, Rules, Suit};
+
+/// Poker hand categories, weakest first. Five of a kind only turns up with wild cards (or a
+/// Camel Cards deck), so it sits above everything.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum PokerHand {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    FiveOfAKind,
+}
+
+/// Where a hand stands under poker rules. Compares by category, then kickers.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct PokerRank {
+    pub category: PokerHand,
+    // Card ranks to break ties with, biggest group first and higher cards first within a size.
+    // Straights only need their top card.
+    pub kickers: Vec<u8>,
+}
+
+/// Ranks `hand` as a poker hand. Cards always count in their natural order, `rules` only
+/// decides which of them are wild. Wild cards become whatever scores best, and take the suit
+/// the rest of the hand shares if there is one. Hands of more than five cards score as the best
+/// five picked from them, the way seven card games do.
+pub fn rank(hand: &Hand, rules: &Rules) -> PokerRank {
+    let suits = hand.suits.as_deref();
+    if hand.cards.len() <= 5 {
+        return rank_cards(&hand.cards, suits, rules);
+    }
+
+    let mut cards = Vec::with_capacity(5);
+    let mut picked_suits = Vec::with_capacity(5);
+    (0..hand.cards.len())
+        .combinations(5)
+        .map(|picked| {
+            cards.clear();
+            cards.extend(picked.iter().map(|&index| hand.cards[index]));
+            picked_suits.clear();
+            if let Some(suits) = suits {
+                picked_suits.extend(picked.iter().map(|&index| suits[index]));
+            }
+            rank_cards(&cards, suits.map(|_| &picked_suits[..]), rules)
+        })
+        .max()
+        .expect("a hand of more than five cards has at least one way to pick five")
+}
+
+fn rank_cards(cards: &[Card], suits: Option<&[Suit]>, rules: &Rules) -> PokerRank {
+    let (wild, fixed): (Vec<usize>, Vec<usize>) =
+        (0..cards.len()).partition(|&index| rules.is_wild(cards[index]));
+
+    // Straights and flushes are five card things, so smaller hands never get them
+    let flush = cards.len() >= 5
+        && suits.is_some_and(|suits| fixed.iter().map(|&index| suits[index]).all_equal());
+
+    let mut cards = fixed.iter().map(|&index| cards[index]).collect::<Vec<_>>();
+    if wild.is_empty() {
+        return score(&cards, flush);
+    }
+
+    // Order doesn't matter, so every multiset of stand-ins is enough
+    let fixed_count = cards.len();
+    Card::ALL
+        .into_iter()
+        .combinations_with_replacement(wild.len())
+        .map(|stand_ins| {
+            cards.truncate(fixed_count);
+            cards.extend(stand_ins);
+            score(&cards, flush)
+        })
+        .max()
+        .expect("there's always at least one way to fill the wild cards")
+}
+
+fn score(cards: &[Card], flush: bool) -> PokerRank {
+    let mut counts = [0usize; 13];
+    for &card in cards {
+        counts[card as usize] += 1;
+    }
+    // (count, rank) pairs, biggest group first and higher cards first within a size
+    let groups = counts
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, count)| count > 0)
+        .map(|(rank, count)| (count, rank as u8))
+        .sorted_unstable_by(|a, b| b.cmp(a))
+        .collect::<Vec<_>>();
+
+    let straight = straight_high(&groups, cards.len());
+    let max_count = groups.first().map_or(0, |group| group.0);
+    let second_max_count = groups.get(1).map_or(0, |group| group.0);
+    let category = match (max_count, second_max_count) {
+        (5.., _) => PokerHand::FiveOfAKind,
+        _ if straight.is_some() && flush => PokerHand::StraightFlush,
+        (4, _) => PokerHand::FourOfAKind,
+        (3, 2..) => PokerHand::FullHouse,
+        _ if flush => PokerHand::Flush,
+        _ if straight.is_some() => PokerHand::Straight,
+        (3, _) => PokerHand::ThreeOfAKind,
+        (2, 2) => PokerHand::TwoPair,
+        (2, _) => PokerHand::OnePair,
+        _ => PokerHand::HighCard,
+    };
+
+    let kickers = match straight {
+        Some(high) => vec![high],
+        None => groups.iter().map(|&(_, rank)| rank).collect(),
+    };
+    PokerRank { category, kickers }
+}
+
+// The top card of a straight, if every card is different and they run on from each other.
+// Aces can also go low, in which case the straight tops out one below the ace-less top.
+fn straight_high(groups: &[(usize, u8)], hand_size: usize) -> Option<u8> {
+    if hand_size < 5 || groups.len() != hand_size {
+        return None;
+    }
+    // `groups` are all singles here, so they're sorted highest card first
+    let high = groups[0].1;
+    let low = groups[hand_size - 1].1;
+    if (high - low) as usize == hand_size - 1 {
+        return Some(high);
+    }
+    let second = groups[1].1;
+    let aces_low = high == Card::Ace as u8 && low == Card::Two as u8;
+    (aces_low && (second - low) as usize == hand_size - 2).then_some(second)
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_core::Line;
+
+    use super::{rank, PokerHand};
+    use crate::{Card, Game, Hand, Rules, Scoring};
+
+    fn category(s: &str, rules: &Rules) -> PokerHand {
+        rank(
+            &Hand::parse(Line::new(7, 1, s), s, rules, None).unwrap(),
+            rules,
+        )
+        .category
+    }
+
+    #[test]
+    fn categories() {
+        let standard = Rules::standard();
+        assert_eq!(category("2h3h4h5h6h", &standard), PokerHand::StraightFlush);
+        assert_eq!(category("23456", &standard), PokerHand::Straight);
+        assert_eq!(category("A2345", &standard), PokerHand::Straight);
+        assert_eq!(category("QKA23", &standard), PokerHand::HighCard);
+        assert_eq!(category("2d7d9dJdKd", &standard), PokerHand::Flush);
+        assert_eq!(category("2d7d9dJdKs", &standard), PokerHand::HighCard);
+        assert_eq!(category("KKKQQ", &standard), PokerHand::FullHouse);
+        assert_eq!(category("AAAAA", &standard), PokerHand::FiveOfAKind);
+        // Too short for straights or flushes
+        assert_eq!(category("2h3h4h", &standard), PokerHand::HighCard);
+
+        // Jokers fill the gap in a straight and take the flush suit
+        let jokers = Rules::jokers_wild();
+        assert_eq!(category("JhKhQhTh9h", &jokers), PokerHand::StraightFlush);
+        assert_eq!(category("JhKhQhTh9s", &jokers), PokerHand::Straight);
+        assert_eq!(category("JJJJJ", &jokers), PokerHand::FiveOfAKind);
+        assert_eq!(category("2233J", &jokers), PokerHand::FullHouse);
+    }
+
+    #[test]
+    fn best_five() {
+        // Seven cards score as the best five of them
+        let standard = Rules::standard();
+        assert_eq!(category("2h4h6h8hTh3d5c", &standard), PokerHand::Flush);
+        assert_eq!(category("23456KK", &standard), PokerHand::Straight);
+        assert_eq!(
+            category("2h3h4h5h6h7d8d", &standard),
+            PokerHand::StraightFlush
+        );
+        assert_eq!(category("KKKQQ22", &standard), PokerHand::FullHouse);
+        assert_eq!(category("2479JQK", &standard), PokerHand::HighCard);
+        // Wild cards still fill gaps among the five picked
+        let jokers = Rules::jokers_wild();
+        assert_eq!(
+            category("4h5h6h8hJh2d3c", &jokers),
+            PokerHand::StraightFlush
+        );
+
+        // Only the best five count as kickers
+        let rank = |s: &str| {
+            rank(
+                &Hand::parse(Line::new(7, 1, s), s, &standard, None).unwrap(),
+                &standard,
+            )
+        };
+        assert_eq!(rank("AAKQJ32").kickers, rank("AAKQJ").kickers);
+        assert_eq!(rank("AAKQJ32"), rank("AAKQJ54"));
+    }
+
+    #[test]
+    fn kickers() {
+        let standard = Rules::standard();
+        let rank = |s: &str| {
+            rank(
+                &Hand::parse(Line::new(7, 1, s), s, &standard, None).unwrap(),
+                &standard,
+            )
+        };
+
+        // The wheel is the lowest straight
+        assert!(rank("A2345") < rank("23456"));
+        assert_eq!(rank("A2345").kickers, [Card::Five as u8]);
+        // Pairs first, then the highest single
+        assert!(rank("992AQ") > rank("99KQJ"));
+        assert!(rank("99KQ2") > rank("99KJ2"));
+        // Where the cards sit makes no difference
+        assert_eq!(rank("KKQQ2"), rank("2QKQK"));
+    }
+
+    #[test]
+    fn corpus() {
+        // Bids number the hands from best to worst under poker rules
+        let input = include_str!("../poker_test_input.txt");
+        let order = |game: &Game| game.bets.iter().map(|bet| bet.amount).collect::<Vec<_>>();
+
+        let camel = input.parse::<Game>().unwrap();
+        assert_eq!(order(&camel), [1, 4, 5, 6, 11, 10, 9, 8, 7, 3, 2]);
+
+        let poker = camel.scored_by(Scoring::Poker);
+        assert_eq!(poker.scoring(), Scoring::Poker);
+        assert_eq!(order(&poker), [11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(
+            poker.winnings().unwrap(),
+            (1..=11).map(|rank| rank * (12 - rank)).sum()
+        );
+
+        // And back again
+        let camel = poker.scored_by(Scoring::CamelCards);
+        assert_eq!(order(&camel), [1, 4, 5, 6, 11, 10, 9, 8, 7, 3, 2]);
+    }
+}
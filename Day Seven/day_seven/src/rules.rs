@@ -0,0 +1,90 @@
+use crate::Card;
+
+/// How cards rank against each other when breaking ties, and which of them are wild.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    // Indexed by `Card as usize`
+    strength: [u8; 13],
+    wild: [bool; 13],
+}
+
+impl Rules {
+    /// Part one: nothing is wild and cards rank 2 up to A.
+    pub fn standard() -> Rules {
+        Rules::custom("23456789TJQKA", "").unwrap()
+    }
+
+    /// Part two: J is wild and is the weakest card when breaking ties.
+    pub fn jokers_wild() -> Rules {
+        Rules::custom("J23456789TQKA", "J").unwrap()
+    }
+
+    /// `order` lists every card once from weakest to strongest, `wildcards` are the cards that
+    /// stand in for whatever makes the best hand. `None` if `order` isn't all thirteen cards or
+    /// either contains something that isn't a card.
+    pub fn custom(order: &str, wildcards: &str) -> Option<Rules> {
+        let mut strength = [None; 13];
+        for (rank, c) in order.chars().enumerate() {
+            let card = Card::from_char(c)?;
+            if strength[card as usize].is_some() {
+                return None;
+            }
+            strength[card as usize] = Some(rank as u8);
+        }
+
+        let mut wild = [false; 13];
+        for c in wildcards.chars() {
+            wild[Card::from_char(c)? as usize] = true;
+        }
+
+        // Every card has to have been given a place
+        let mut ranks = [0; 13];
+        for (rank, placed) in ranks.iter_mut().zip(strength) {
+            *rank = placed?;
+        }
+
+        Some(Rules {
+            strength: ranks,
+            wild,
+        })
+    }
+
+    pub fn strength(&self, card: Card) -> u8 {
+        self.strength[card as usize]
+    }
+
+    pub fn is_wild(&self, card: Card) -> bool {
+        self.wild[card as usize]
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::standard()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rules;
+    use crate::Card;
+
+    #[test]
+    fn custom() {
+        let rules = Rules::jokers_wild();
+        assert_eq!(rules.strength(Card::Jack), 0);
+        assert_eq!(rules.strength(Card::Ace), 12);
+        assert!(rules.is_wild(Card::Jack));
+        assert!(!rules.is_wild(Card::Queen));
+
+        // Missing a card, a repeat, and something that isn't a card
+        assert_eq!(Rules::custom("23456789TJQK", ""), None);
+        assert_eq!(Rules::custom("23456789TJQKK", ""), None);
+        assert_eq!(Rules::custom("23456789TJQKA", "x"), None);
+
+        // Twos are wild but still rank where they normally would
+        let rules = Rules::custom("23456789TJQKA", "2").unwrap();
+        assert_eq!(rules.strength(Card::Two), 0);
+        assert!(rules.is_wild(Card::Two));
+    }
+}
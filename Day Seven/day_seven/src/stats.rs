@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+
+use rand::Rng;
+
+use crate::{poker, Card, Game, Hand, HandState, PokerRank, Rules, Scoring, WinningsOverflow};
+
+/// How much one bet's hand matters to the game's total winnings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sensitivity {
+    pub rank: usize,
+    /// The biggest drop in total winnings from changing any one of its cards, zero if nothing
+    /// makes it lose out
+    pub worst: i128,
+    /// The biggest rise from changing any one of its cards, zero if nothing helps
+    pub best: i128,
+}
+
+// Where a hand sorts in a game. Equal hands go in the order of the lines they came from.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Position {
+    poker: Option<PokerRank>,
+    hand: Hand,
+    line: usize,
+}
+
+impl Game {
+    /// How many hands fall in each category.
+    pub fn histogram(&self) -> BTreeMap<HandState, usize> {
+        let mut histogram = BTreeMap::new();
+        for bet in &self.bets {
+            *histogram.entry(bet.hand.state).or_default() += 1;
+        }
+        histogram
+    }
+
+    /// How much of `winnings` each category's bets make up.
+    pub fn winnings_by_category(&self) -> Result<BTreeMap<HandState, u64>, WinningsOverflow> {
+        let mut by_category = BTreeMap::new();
+        for (index, bet) in self.bets.iter().enumerate() {
+            let total: &mut u64 = by_category.entry(bet.hand.state).or_default();
+            *total = bet
+                .winnings(index)
+                .and_then(|winnings| total.checked_add(winnings))
+                .ok_or(WinningsOverflow)?;
+        }
+        Ok(by_category)
+    }
+
+    /// For every bet, weakest first, how far the total winnings could move if one card in its
+    /// hand were any other card.
+    pub fn sensitivity(&self) -> Vec<Sensitivity> {
+        let positions = self
+            .bets
+            .iter()
+            .map(|bet| self.position(bet.hand.clone(), bet.line))
+            .collect::<Vec<_>>();
+        // The total of every bid before each position
+        let mut bids_before = vec![0];
+        for bet in &self.bets {
+            bids_before.push(bids_before.last().unwrap() + bet.amount as i128);
+        }
+
+        self.bets
+            .iter()
+            .enumerate()
+            .map(|(index, bet)| {
+                let (mut worst, mut best) = (0, 0);
+                for slot in 0..bet.hand.cards.len() {
+                    for card in Card::ALL {
+                        if card == bet.hand.cards[slot] {
+                            continue;
+                        }
+                        let mut cards = bet.hand.cards.clone();
+                        cards[slot] = card;
+                        let mut hand = Hand::new(cards, &self.rules);
+                        hand.suits.clone_from(&bet.hand.suits);
+
+                        // Where it would land among everyone else
+                        let position = self.position(hand, bet.line);
+                        let mut moved_to = positions.partition_point(|other| other < &position);
+                        if moved_to > index {
+                            moved_to -= 1;
+                        }
+
+                        let amount = bet.amount as i128;
+                        // Everyone it passes shifts a rank the other way
+                        let change = if moved_to > index {
+                            amount * (moved_to - index) as i128
+                                - (bids_before[moved_to + 1] - bids_before[index + 1])
+                        } else {
+                            (bids_before[index] - bids_before[moved_to])
+                                - amount * (index - moved_to) as i128
+                        };
+                        worst = change.min(worst);
+                        best = change.max(best);
+                    }
+                }
+                Sensitivity {
+                    rank: index + 1,
+                    worst,
+                    best,
+                }
+            })
+            .collect()
+    }
+
+    fn position(&self, hand: Hand, line: usize) -> Position {
+        Position {
+            poker: (self.scoring == Scoring::Poker).then(|| poker::rank(&hand, &self.rules)),
+            hand,
+            line,
+        }
+    }
+}
+
+/// Roughly how often each category comes up in random hands of `hand_size` cards, as a share of
+/// `samples` draws. Every card is drawn from a full set of thirteen, the way puzzle hands can
+/// have five of a kind.
+pub fn estimate_categories(
+    rng: &mut impl Rng,
+    rules: &Rules,
+    hand_size: usize,
+    samples: usize,
+) -> BTreeMap<HandState, f64> {
+    let mut counts = BTreeMap::new();
+    let mut cards = Vec::with_capacity(hand_size);
+    for _ in 0..samples {
+        cards.clear();
+        cards.extend((0..hand_size).map(|_| Card::ALL[rng.gen_range(0..Card::ALL.len())]));
+        *counts
+            .entry(HandState::from_cards(&cards, rules))
+            .or_insert(0) += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(state, count)| (state, count as f64 / samples as f64))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use itertools::Itertools;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{estimate_categories, Sensitivity};
+    use crate::{Card, Game, HandState, Rules, Scoring};
+
+    #[test]
+    fn histogram() {
+        let game = include_str!("../test_input.txt").parse::<Game>().unwrap();
+        assert_eq!(
+            game.histogram().into_iter().collect::<Vec<_>>(),
+            [
+                (HandState::OnePair, 1),
+                (HandState::TwoPair, 2),
+                (HandState::ThreeOfAKind, 2)
+            ]
+        );
+        let by_category = game.winnings_by_category().unwrap();
+        assert_eq!(
+            by_category.clone().into_iter().collect::<Vec<_>>(),
+            [
+                (HandState::OnePair, 765),
+                (HandState::TwoPair, 220 * 2 + 28 * 3),
+                (HandState::ThreeOfAKind, 684 * 4 + 483 * 5)
+            ]
+        );
+        assert_eq!(by_category.values().sum::<u64>(), game.winnings().unwrap());
+    }
+
+    // Changes every card in turn and rescores the whole game
+    fn sensitivity_by_rescoring(input: &str, rules: &Rules, scoring: Scoring) -> Vec<Sensitivity> {
+        let score = |input: &str| {
+            Game::from_str_with_rules(input, rules)
+                .unwrap()
+                .scored_by(scoring)
+        };
+        let game = score(input);
+        let total = game.winnings().unwrap() as i128;
+        game.bets
+            .iter()
+            .enumerate()
+            .map(|(index, bet)| {
+                let (mut worst, mut best) = (0, 0);
+                for slot in 0..bet.hand.cards.len() {
+                    for card in Card::ALL {
+                        if card == bet.hand.cards[slot] {
+                            continue;
+                        }
+                        let changed = input
+                            .lines()
+                            .enumerate()
+                            .map(|(line, text)| {
+                                let mut text = text.chars().collect::<Vec<_>>();
+                                if line + 1 == bet.line {
+                                    text[slot] = card.to_char();
+                                }
+                                text.into_iter().collect::<String>()
+                            })
+                            .join("\n");
+                        let change = score(&changed).winnings().unwrap() as i128 - total;
+                        worst = change.min(worst);
+                        best = change.max(best);
+                    }
+                }
+                Sensitivity {
+                    rank: index + 1,
+                    worst,
+                    best,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sensitivity() {
+        // Repeated hands too, so ties have to be settled the same way
+        let input = format!(
+            "{}\n32T3K 100\nKK677 7\n23456 40",
+            include_str!("../test_input.txt").trim_end()
+        );
+        for rules in [Rules::standard(), Rules::jokers_wild()] {
+            for scoring in [Scoring::CamelCards, Scoring::Poker] {
+                let game = Game::from_str_with_rules(&input, &rules)
+                    .unwrap()
+                    .scored_by(scoring);
+                assert_eq!(
+                    game.sensitivity(),
+                    sensitivity_by_rescoring(&input, &rules, scoring),
+                    "{rules:?} {scoring:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn monte_carlo() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let estimate = estimate_categories(&mut rng, &Rules::standard(), 5, 100_000);
+        assert!((estimate.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        // Exactly 13·12·11·10·9 / 13⁵ and 13·10·12·11·10 / 13⁵
+        assert!((estimate[&HandState::HighCard] - 0.416).abs() < 0.01);
+        assert!((estimate[&HandState::OnePair] - 0.462).abs() < 0.01);
+
+        // Jokers make high cards rarer, 12·11·10·9·8 / 13⁵
+        let estimate = estimate_categories(&mut rng, &Rules::jokers_wild(), 5, 100_000);
+        assert!((estimate[&HandState::HighCard] - 0.256).abs() < 0.01);
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{Race, RaceNumber};
+
+/// How a boat turns button holding into distance.
+///
+/// Models that know where their best hold is get a binary search either side of it, anything
+/// else has every hold checked one by one.
+pub trait BoatModel<T: RaceNumber> {
+    /// How far the boat goes when the button is held for `hold_time_ms` of a `time_ms` race.
+    fn distance(&self, hold_time_ms: &T, time_ms: &T) -> T;
+
+    /// The hold that goes furthest, for models whose distance never falls before it and never
+    /// rises after it. `None` means there's no such promise.
+    fn best_hold(&self, _time_ms: &T) -> Option<T> {
+        None
+    }
+
+    fn does_win(&self, race: &Race<T>, hold_time_ms: &T) -> bool {
+        hold_time_ms <= &race.time_ms
+            && self.distance(hold_time_ms, &race.time_ms) > race.distance_record_mm
+    }
+
+    fn win_count(&self, race: &Race<T>) -> T {
+        match self.best_hold(&race.time_ms) {
+            Some(best) => unimodal_win_count(self, race, best),
+            None => search_win_count(self, race),
+        }
+    }
+}
+
+/// The puzzle's own boat, every millisecond held adds 1mm/ms of speed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Standard;
+
+impl<T: RaceNumber> BoatModel<T> for Standard {
+    fn distance(&self, hold_time_ms: &T, time_ms: &T) -> T {
+        if hold_time_ms > time_ms {
+            return T::zero();
+        }
+        hold_time_ms.clone() * (time_ms.clone() - hold_time_ms.clone())
+    }
+
+    fn best_hold(&self, time_ms: &T) -> Option<T> {
+        Some(time_ms.clone() / (T::one() + T::one()))
+    }
+
+    fn does_win(&self, race: &Race<T>, hold_time_ms: &T) -> bool {
+        race.does_win(hold_time_ms.clone())
+    }
+
+    fn win_count(&self, race: &Race<T>) -> T {
+        race.win_count()
+    }
+}
+
+/// A boat with its knobs exposed.
+///
+/// Speed is `charge_rate` per millisecond held, stops growing at `max_speed`, and has `drag`
+/// knocked off it for the whole run. Every millisecond held also costs `hold_cost` millimetres.
+/// Speeds and distances bottom out at zero rather than going backwards.
+#[derive(Debug, Clone)]
+pub struct Tuned<T> {
+    pub charge_rate: T,
+    pub max_speed: Option<T>,
+    pub drag: T,
+    pub hold_cost: T,
+}
+
+impl<T: RaceNumber> Default for Tuned<T> {
+    // Behaves exactly like `Standard`
+    fn default() -> Self {
+        Tuned {
+            charge_rate: T::one(),
+            max_speed: None,
+            drag: T::zero(),
+            hold_cost: T::zero(),
+        }
+    }
+}
+
+impl<T: RaceNumber> BoatModel<T> for Tuned<T> {
+    fn distance(&self, hold_time_ms: &T, time_ms: &T) -> T {
+        if hold_time_ms > time_ms {
+            return T::zero();
+        }
+        let mut speed = self.charge_rate.clone() * hold_time_ms.clone();
+        if let Some(max_speed) = &self.max_speed {
+            speed = speed.min(max_speed.clone());
+        }
+        let speed = saturating_sub(speed, self.drag.clone());
+        let travelled = speed * (time_ms.clone() - hold_time_ms.clone());
+        saturating_sub(travelled, self.hold_cost.clone() * hold_time_ms.clone())
+    }
+
+    fn best_hold(&self, time_ms: &T) -> Option<T> {
+        if self.charge_rate.is_zero() {
+            return Some(T::zero());
+        }
+        let two = T::one() + T::one();
+        // Below the speed cap the distance is the parabola
+        // -rate h^2 + (rate T + drag - cost) h - drag T, which peaks at its vertex
+        let vertex = saturating_sub(
+            self.charge_rate.clone() * time_ms.clone() + self.drag.clone(),
+            self.hold_cost.clone(),
+        ) / (two * self.charge_rate.clone());
+        let mut candidates = vec![vertex.clone(), vertex + T::one()];
+        // Past the cap it only goes down, so the best might be either side of where it kicks in
+        if let Some(max_speed) = &self.max_speed {
+            let capped = (max_speed.clone() + self.charge_rate.clone() - T::one())
+                / self.charge_rate.clone();
+            if !capped.is_zero() {
+                candidates.push(capped.clone() - T::one());
+            }
+            candidates.push(capped);
+        }
+        candidates
+            .into_iter()
+            .map(|hold_time_ms| hold_time_ms.min(time_ms.clone()))
+            .max_by_key(|hold_time_ms| self.distance(hold_time_ms, time_ms))
+    }
+}
+
+/// Any distance function at all. Nothing is assumed about its shape, so every hold gets checked.
+pub struct Custom<F>(pub F);
+
+impl<T: RaceNumber, F: Fn(&T, &T) -> T> BoatModel<T> for Custom<F> {
+    fn distance(&self, hold_time_ms: &T, time_ms: &T) -> T {
+        (self.0)(hold_time_ms, time_ms)
+    }
+}
+
+// Binary searches out from the best hold, the distance never falls on the way up to it and never
+// rises after it so the winners are one unbroken run
+fn unimodal_win_count<T, M>(model: &M, race: &Race<T>, best: T) -> T
+where
+    T: RaceNumber,
+    M: BoatModel<T> + ?Sized,
+{
+    if !model.does_win(race, &best) {
+        return T::zero();
+    }
+    let lowest = first_where(T::zero(), best.clone(), |hold| model.does_win(race, hold));
+    let past_highest = first_where(best, race.time_ms.clone() + T::one(), |hold| {
+        !model.does_win(race, hold)
+    });
+    past_highest - lowest
+}
+
+// The first value in `low..=high` where `found` holds, given it stays true once it's true.
+// Returns `high` if it never does.
+fn first_where<T: RaceNumber>(mut low: T, mut high: T, found: impl Fn(&T) -> bool) -> T {
+    let two = T::one() + T::one();
+    while low < high {
+        let mid = low.clone() + (high.clone() - low.clone()) / two.clone();
+        if found(&mid) {
+            high = mid;
+        } else {
+            low = mid + T::one();
+        }
+    }
+    low
+}
+
+/// Checks every hold from 0 to the end of the race.
+pub fn search_win_count<T, M>(model: &M, race: &Race<T>) -> T
+where
+    T: RaceNumber,
+    M: BoatModel<T> + ?Sized,
+{
+    let mut count = T::zero();
+    let mut hold_time_ms = T::zero();
+    while hold_time_ms <= race.time_ms {
+        if model.does_win(race, &hold_time_ms) {
+            count = count + T::one();
+        }
+        hold_time_ms = hold_time_ms + T::one();
+    }
+    count
+}
+
+fn saturating_sub<T: RaceNumber>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        T::zero()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num::BigUint;
+
+    use super::{search_win_count, BoatModel, Custom, Standard, Tuned};
+    use crate::Race;
+
+    fn races() -> impl Iterator<Item = Race<u64>> {
+        (0..40u64).flat_map(|time_ms| {
+            (0..=time_ms * time_ms / 4 + 1)
+                .step_by(3)
+                .map(move |record| Race::new(time_ms, record))
+        })
+    }
+
+    #[test]
+    fn standard() {
+        let race = Race::new(30u64, 200);
+        assert_eq!(Standard.win_count(&race), 9);
+        assert_eq!(Tuned::default().win_count(&race), 9);
+        assert!(Standard.does_win(&race, &11));
+        assert!(!Standard.does_win(&race, &10));
+    }
+
+    #[test]
+    fn tuned_fast_path_matches_search() {
+        let models = [
+            Tuned::default(),
+            Tuned {
+                charge_rate: 3,
+                ..Tuned::default()
+            },
+            Tuned {
+                max_speed: Some(7),
+                ..Tuned::default()
+            },
+            Tuned {
+                charge_rate: 2,
+                max_speed: Some(9),
+                drag: 3,
+                hold_cost: 0,
+            },
+            Tuned {
+                drag: 4,
+                hold_cost: 5,
+                ..Tuned::default()
+            },
+            Tuned {
+                charge_rate: 0,
+                ..Tuned::default()
+            },
+            Tuned {
+                charge_rate: 5,
+                max_speed: Some(12),
+                drag: 2,
+                hold_cost: 30,
+            },
+        ];
+
+        for model in &models {
+            for race in races() {
+                assert_eq!(
+                    model.win_count(&race),
+                    search_win_count(model, &race),
+                    "{model:?} in {race:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn custom_is_searched() {
+        // Only every third hold moves at all, so the winners come in separate bunches
+        let jumpy = Custom(|hold: &u64, time: &u64| {
+            if hold.is_multiple_of(3) {
+                hold * (time - hold)
+            } else {
+                0
+            }
+        });
+        let race = Race::new(30u64, 200);
+        assert_eq!(jumpy.win_count(&race), 3);
+        assert!(jumpy.does_win(&race, &12));
+        assert!(!jumpy.does_win(&race, &13));
+    }
+
+    #[test]
+    fn big_numbers() {
+        let race = Race::<BigUint>::part_2_from_str(include_str!("../test_input.txt")).unwrap();
+        let tuned = Tuned::<BigUint>::default();
+        assert_eq!(tuned.win_count(&race), BigUint::from(71503u32));
+    }
+}
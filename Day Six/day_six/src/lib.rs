@@ -0,0 +1,541 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use aoc_core::{Answer, Line, ParseError, PartError, Solution};
+use num::{integer::Roots, CheckedMul, Num, ToPrimitive};
+
+pub mod boat;
+pub mod report;
+
+pub use num::BigUint;
+pub use report::RaceReport;
+
+/// Anything a race can be measured in, from `u32` up to `BigUint`.
+pub trait RaceNumber: Num + Roots + CheckedMul + Clone + Ord + FromStr {}
+
+impl<T> RaceNumber for T where T: Num + Roots + CheckedMul + Clone + Ord + FromStr {}
+
+#[derive(Debug)]
+pub struct Races<T>(Vec<Race<T>>);
+
+impl<T: RaceNumber> FromStr for Races<T> {
+    type Err = ParseError;
+
+    // Only the separate columns have to fit in T, the kerned race is part two's problem
+    fn from_str(s: &str) -> Result<Races<T>, ParseError> {
+        let (times, dists) = sheet_rows(s)?;
+        races(&times, &dists)
+    }
+}
+
+// The labelled time and distance rows of the race sheet
+fn sheet_rows(s: &str) -> Result<(SheetRow<'_>, SheetRow<'_>), ParseError> {
+    let mut lines = aoc_core::parse::lines(6, s);
+    let time_line = lines
+        .next()
+        .ok_or_else(|| ParseError::missing_line(6, 1, "`Time:`"))?;
+    let times = SheetRow::new(time_line, "Time:")?;
+    let dist_line = lines
+        .next()
+        .ok_or_else(|| ParseError::missing_line(6, 2, "`Distance:`"))?;
+    let dists = SheetRow::new(dist_line, "Distance:")?;
+    Ok((times, dists))
+}
+
+// One labelled row of the sheet, which can be read as separate columns or all run together
+struct SheetRow<'a> {
+    line: Line<'a>,
+    numbers: &'a str,
+    tokens: Vec<&'a str>,
+}
+
+impl<'a> SheetRow<'a> {
+    fn new(line: Line<'a>, header: &str) -> Result<SheetRow<'a>, ParseError> {
+        let numbers = line.expect_prefix(line.text, header)?;
+        let tokens = numbers.split_whitespace().collect::<Vec<_>>();
+        if tokens.is_empty() {
+            return Err(line.error_at_end("a number"));
+        }
+        Ok(SheetRow {
+            line,
+            numbers,
+            tokens,
+        })
+    }
+
+    fn columns<T: RaceNumber>(&self) -> Result<Vec<T>, ParseError> {
+        self.tokens
+            .iter()
+            .map(|token| self.line.parse(token, "a number"))
+            .collect()
+    }
+
+    // Every column run together, ignoring the spaces between them
+    fn kerned<T: RaceNumber>(&self) -> Result<T, ParseError> {
+        if let Some(token) = self
+            .tokens
+            .iter()
+            .find(|token| !token.bytes().all(|byte| byte.is_ascii_digit()))
+        {
+            return Err(self.line.error_at(token, "a number"));
+        }
+        self.tokens.concat().parse().map_err(|_| {
+            self.line
+                .error_at(self.numbers.trim(), "a number that fits the race")
+        })
+    }
+}
+
+fn kerned_race<T: RaceNumber>(times: &SheetRow, dists: &SheetRow) -> Result<Race<T>, ParseError> {
+    Ok(Race::new(times.kerned()?, dists.kerned()?))
+}
+
+// Every race needs both a time and a record, don't quietly drop the odd one out
+fn races<T: RaceNumber>(times: &SheetRow, dists: &SheetRow) -> Result<Races<T>, ParseError> {
+    let time_columns = times.columns::<T>()?;
+    let dist_columns = dists.columns::<T>()?;
+    let races = time_columns.len();
+    if let Some(extra) = dists.tokens.get(races) {
+        return Err(dists
+            .line
+            .error_at(extra, format!("{races} distances, one for each time")));
+    }
+    if dist_columns.len() < races {
+        return Err(dists
+            .line
+            .error_at_end(format!("{races} distances, one for each time")));
+    }
+
+    Ok(Races(
+        time_columns
+            .into_iter()
+            .zip(dist_columns)
+            .map(|(time_ms, distance_record_mm)| Race::new(time_ms, distance_record_mm))
+            .collect(),
+    ))
+}
+
+impl<T: RaceNumber> Races<T> {
+    pub fn part_1(&self) -> T {
+        self.0
+            .iter()
+            .map(Race::win_count)
+            .fold(T::one(), |product, count| product * count)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Race<T> {
+    time_ms: T,
+    distance_record_mm: T,
+}
+
+impl<T: RaceNumber> Race<T> {
+    pub fn new(time_ms: T, distance_record_mm: T) -> Race<T> {
+        Race {
+            time_ms,
+            distance_record_mm,
+        }
+    }
+
+    /// Reads the sheet the part two way, every row as one badly kerned number.
+    pub fn part_2_from_str(s: &str) -> Result<Race<T>, ParseError> {
+        let (times, dists) = sheet_rows(s)?;
+        kerned_race(&times, &dists)
+    }
+
+    pub fn does_win(&self, hold_time_ms: T) -> bool {
+        if hold_time_ms.is_zero() || hold_time_ms >= self.time_ms {
+            return false;
+        }
+        // h * (T - h) > D without working out the product, so fixed width types can't overflow
+        let remaining_ms = self.time_ms.clone() - hold_time_ms.clone();
+        remaining_ms > self.distance_record_mm.clone() / hold_time_ms
+    }
+
+    // Holding for h wins when h * (T - h) > D, so the winners sit strictly between the roots of
+    // h^2 - Th + D = 0. The square root is only used as a first guess, the boundary is then nudged
+    // with exact integer checks so rounding can never let a loser in or leave a winner out.
+    pub fn win_count(&self) -> T {
+        match self.winning_holds() {
+            Some((lowest, highest)) => highest - lowest + T::one(),
+            None => T::zero(),
+        }
+    }
+
+    /// The shortest and longest holds that beat the record, if any do.
+    pub fn winning_holds(&self) -> Option<(T, T)> {
+        let two = T::one() + T::one();
+        let time_ms = &self.time_ms;
+        let half = time_ms.clone() / two.clone();
+
+        let mut lowest = match time_ms.checked_mul(time_ms) {
+            Some(square) => {
+                let four_d = (two.clone() * two.clone()).checked_mul(&self.distance_record_mm);
+                match four_d {
+                    Some(four_d) if four_d < square => {
+                        (time_ms.clone() - (square - four_d).sqrt()) / two
+                    }
+                    // Either the best hold only ties the record or the record is out of reach
+                    _ => return None,
+                }
+            }
+            // T^2 doesn't fit in T, fall back to a binary search on the rising half
+            None => self.lowest_winner(half.clone())?,
+        };
+
+        // Both the root and the halving round down, so this is at most a couple of steps either way
+        while lowest <= half && !self.does_win(lowest.clone()) {
+            lowest = lowest + T::one();
+        }
+        while !lowest.is_zero() && self.does_win(lowest.clone() - T::one()) {
+            lowest = lowest - T::one();
+        }
+        if lowest > half {
+            return None;
+        }
+
+        // The distance is symmetric about T / 2, so the highest winner mirrors the lowest
+        let highest = time_ms.clone() - lowest.clone();
+        Some((lowest, highest))
+    }
+
+    pub fn report(&self) -> RaceReport<T> {
+        let best_hold_ms = self.time_ms.clone() / (T::one() + T::one());
+        let best_distance_mm =
+            best_hold_ms.checked_mul(&(self.time_ms.clone() - best_hold_ms.clone()));
+        let margin_mm = best_distance_mm.as_ref().map(|best_distance_mm| {
+            if best_distance_mm > &self.distance_record_mm {
+                best_distance_mm.clone() - self.distance_record_mm.clone()
+            } else {
+                T::zero()
+            }
+        });
+        RaceReport {
+            time_ms: self.time_ms.clone(),
+            distance_record_mm: self.distance_record_mm.clone(),
+            winning_holds: self.winning_holds(),
+            win_count: self.win_count(),
+            best_hold_ms,
+            best_distance_mm,
+            margin_mm,
+        }
+    }
+
+    // The first winning hold in 0..=half, where the distance only ever goes up
+    fn lowest_winner(&self, half: T) -> Option<T> {
+        if !self.does_win(half.clone()) {
+            return None;
+        }
+        let two = T::one() + T::one();
+        let (mut low, mut high) = (T::zero(), half);
+        while low < high {
+            let mid = (low.clone() + high.clone()) / two.clone();
+            if self.does_win(mid.clone()) {
+                high = mid;
+            } else {
+                low = mid + T::one();
+            }
+        }
+        Some(low)
+    }
+}
+
+// How far the boat goes when the button is held for `hold_time_ms` of the race
+pub fn ready_set_go<T>(hold_time_ms: T, time_limit_ms: T) -> T
+where
+    T: std::ops::Sub<Output = T> + std::ops::Mul<Output = T> + Copy,
+{
+    hold_time_ms * (time_limit_ms - hold_time_ms)
+}
+
+/// Both readings of the sheet, the separate races for part one and the badly kerned single race
+/// for part two. The kerned race is kept as an error when it's too big for T, so part one can
+/// still be answered.
+#[derive(Debug)]
+pub struct RaceSheet<T = u64> {
+    pub races: Races<T>,
+    pub race: Result<Race<T>, ParseError>,
+}
+
+impl<T: RaceNumber> FromStr for RaceSheet<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<RaceSheet<T>, ParseError> {
+        let (times, dists) = sheet_rows(s)?;
+        Ok(RaceSheet {
+            races: races(&times, &dists)?,
+            race: kerned_race(&times, &dists),
+        })
+    }
+}
+
+impl<T: RaceNumber + Display> RaceSheet<T> {
+    /// A report row for every race on the sheet, followed by the kerned race if it fits.
+    pub fn report_table(&self) -> String {
+        let mut rows = self
+            .races
+            .0
+            .iter()
+            .enumerate()
+            .map(|(index, race)| ((index + 1).to_string(), race.report()))
+            .collect::<Vec<_>>();
+        if let Ok(race) = &self.race {
+            rows.push(("kerned".to_string(), race.report()));
+        }
+        report::table(&rows)
+    }
+}
+
+// Read in `BigUint` so the kerned race fits however many races the sheet has
+impl Solution for RaceSheet<BigUint> {
+    const DAY: u8 = 6;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer, PartError> {
+        Ok(answer(self.races.part_1()))
+    }
+
+    fn part_two(&self) -> Result<Answer, PartError> {
+        let race = self.race.as_ref().map_err(Clone::clone)?;
+        Ok(answer(race.win_count()))
+    }
+}
+
+// Counts past u64 still get printed, just as text
+fn answer(count: BigUint) -> Answer {
+    match count.to_u64() {
+        Some(count) => count.into(),
+        None => count.to_string().into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Race, RaceSheet, Races};
+    use num::BigUint;
+    use std::str::FromStr;
+
+    #[test]
+    fn parse() {
+        let input = include_str!("../test_input.txt");
+        let races = Races::<u32>::from_str(input).unwrap();
+        let first = races.0.first().unwrap();
+        assert_eq!(first.time_ms, 7);
+        assert_eq!(first.distance_record_mm, 9);
+        assert_eq!(first.win_count(), 4);
+
+        let second = &races.0[1];
+        assert_eq!(second.time_ms, 15);
+        assert_eq!(second.distance_record_mm, 40);
+        assert_eq!(second.win_count(), 8);
+
+        let third = races.0.last().unwrap();
+        assert_eq!(third.time_ms, 30);
+        assert_eq!(third.distance_record_mm, 200);
+        assert_eq!(third.win_count(), 9);
+
+        assert_eq!(races.part_1(), 4 * 8 * 9)
+    }
+
+    #[test]
+    fn ready_set_go() {
+        assert_eq!(super::ready_set_go(1, 7), 6);
+        assert_eq!(super::ready_set_go(2, 7), 10);
+    }
+
+    #[test]
+    fn test_part2_parse() {
+        let race = Race::<u64>::part_2_from_str(include_str!("../test_input.txt")).unwrap();
+
+        assert_eq!(race.distance_record_mm, 940200);
+        assert_eq!(race.time_ms, 71530);
+        assert_eq!(race.win_count(), 71503)
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err =
+            Races::<u32>::from_str("Time:      7  15   30\nDistance:  9  4O  200").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 6, line 2, column 15: expected a number, found `4O`"
+        );
+
+        let err = Race::<u64>::part_2_from_str("Time:      7  15   30").unwrap_err();
+        assert_eq!(err.line, 2);
+
+        let err =
+            Race::<u32>::part_2_from_str("Time: 99999999999 99999999999\nDistance: 1").unwrap_err();
+        assert_eq!(err.column, 7);
+        let err = Race::<u64>::part_2_from_str("Time: 7 15\nDistance: 9 4O").unwrap_err();
+        assert_eq!(err.column, 13);
+    }
+
+    #[test]
+    fn kerned_overflow() {
+        // A real sheet, where the separate races fit a u32 easily but the kerned distance doesn't
+        let input = "Time: 44 89 96 91\nDistance: 277 1136 1890 1768";
+        let races = input.parse::<Races<u32>>().unwrap();
+        assert_eq!(races.0.len(), 4);
+        assert_eq!(races.0[3].distance_record_mm, 1768);
+
+        let err = Race::<u32>::part_2_from_str(input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 6, line 2, column 11: expected a number that fits the race, \
+             found `277 1136 1890 1768`"
+        );
+        // The sheet still reads, only the kerned race is missing
+        let sheet = input.parse::<RaceSheet<u32>>().unwrap();
+        assert_eq!(sheet.races.part_1(), races.part_1());
+        assert_eq!(sheet.race.as_ref().unwrap_err(), &err);
+        assert_eq!(sheet.report_table().lines().count(), 6);
+
+        let sheet = input.parse::<RaceSheet<u64>>().unwrap();
+        let race = sheet.race.unwrap();
+        assert_eq!(race.distance_record_mm, 277113618901768);
+        assert_eq!(race.time_ms, 44899691);
+    }
+
+    // The old linear scan from both ends, kept to check the closed form against
+    fn brute_force(time_ms: u64, distance_record_mm: u64) -> u64 {
+        (0..=time_ms)
+            .filter(|&hold_time_ms| super::ready_set_go(hold_time_ms, time_ms) > distance_record_mm)
+            .count() as u64
+    }
+
+    #[test]
+    fn win_count_matches_brute_force() {
+        for time_ms in 0..60u64 {
+            // Every record from "anything wins" to "nothing can"
+            for distance_record_mm in 0..=(time_ms * time_ms / 4 + 1) {
+                let race = Race::new(time_ms, distance_record_mm);
+                assert_eq!(
+                    race.win_count(),
+                    brute_force(time_ms, distance_record_mm),
+                    "time {time_ms}, record {distance_record_mm}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn win_count_edges() {
+        let count = |time_ms: u64, distance_record_mm: u64| {
+            Race::new(time_ms, distance_record_mm).win_count()
+        };
+
+        // The record sits exactly on a root, holding 2 or 8 only ties
+        assert_eq!(count(10, 16), 5);
+        assert_eq!(brute_force(10, 16), 5);
+        // The best possible hold only ties the record
+        assert_eq!(count(10, 25), 0);
+        assert_eq!(count(11, 30), 0);
+        assert_eq!(count(11, 29), 2);
+        assert_eq!(count(0, 0), 0);
+        assert_eq!(count(1, 0), 0);
+        assert_eq!(count(2, 0), 1);
+
+        // Big enough that a float root would round the wrong way
+        let time_ms = u64::MAX;
+        let half = time_ms / 2;
+        let best = half as u128 * (time_ms - half) as u128;
+        assert_eq!(count(time_ms, 0), time_ms - 1);
+        let count = |time_ms: u128, distance_record_mm: u128| {
+            Race::new(time_ms, distance_record_mm).win_count()
+        };
+        assert_eq!(count(time_ms as u128, best - 1), 2);
+        assert_eq!(count(time_ms as u128, best), 0);
+        // Same again in u64, where T^2 no longer fits and the binary search takes over
+        let best = best as u64 / 4;
+        assert_eq!(
+            Race::new(time_ms, best).win_count(),
+            closed_form_u128(time_ms, best)
+        );
+    }
+
+    // Counting through the closed form in u128 for a u64 race
+    fn closed_form_u128(time_ms: u64, distance_record_mm: u64) -> u64 {
+        Race::new(time_ms as u128, distance_record_mm as u128).win_count() as u64
+    }
+
+    #[test]
+    fn wider_than_u64() {
+        // Kerned together these are way past u64::MAX
+        let input = "Time:      71530 71530 71530 71530\nDistance:  940200 940200 940200 940200";
+        let race = Race::<u128>::part_2_from_str(input).unwrap();
+        let big = Race::<BigUint>::part_2_from_str(input).unwrap();
+        assert_eq!(big.win_count(), BigUint::from(race.win_count()));
+        assert!(race.win_count() > u64::MAX as u128);
+
+        let races = Races::<BigUint>::from_str(include_str!("../test_input.txt")).unwrap();
+        assert_eq!(races.part_1(), BigUint::from(4u32 * 8 * 9));
+    }
+
+    #[test]
+    fn solution_past_u64() {
+        use aoc_core::{Answer, Solution};
+
+        let input = "Time:      71530 71530 71530 71530\nDistance:  940200 940200 940200 940200";
+        let sheet = RaceSheet::<BigUint>::parse(input).unwrap();
+        let expected = Race::<u128>::part_2_from_str(input).unwrap().win_count();
+        assert_eq!(sheet.part_two(), Ok(Answer::Text(expected.to_string())));
+        assert_eq!(
+            sheet.part_one(),
+            Ok(Answer::Text(71503u128.pow(4).to_string()))
+        );
+
+        let sheet = RaceSheet::<BigUint>::parse(include_str!("../test_input.txt")).unwrap();
+        assert_eq!(sheet.part_two(), Ok(71503u64.into()));
+    }
+
+    #[test]
+    fn both_readings() {
+        let sheet = include_str!("../test_input.txt")
+            .parse::<RaceSheet>()
+            .unwrap();
+        assert_eq!(sheet.races.0.len(), 3);
+        assert_eq!(sheet.races.part_1(), 288);
+        let race = sheet.race.unwrap();
+        assert_eq!(race.time_ms, 71530);
+        assert_eq!(race.distance_record_mm, 940200);
+    }
+
+    #[test]
+    fn sheet_errors() {
+        let err = "Tim: 7 15\nDistance: 9 40"
+            .parse::<RaceSheet>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 6, line 1, column 1: expected `Time:`, found `Tim: 7 15`"
+        );
+
+        let err = "Time: 7 15\nDist: 9 40".parse::<RaceSheet>().unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (2, "`Distance:`"));
+
+        let err = "Time: 7 15\nDistance: 9 40 200"
+            .parse::<RaceSheet>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 6, line 2, column 16: expected 2 distances, one for each time, found `200`"
+        );
+
+        let err = "Time: 7 15 30\nDistance: 9 40"
+            .parse::<RaceSheet>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 6, line 2, column 15: expected 3 distances, one for each time, \
+             found the end of the line"
+        );
+
+        let err = "Time:\nDistance: 9".parse::<RaceSheet>().unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt::Display;
+
+/// Everything worth knowing about one race at a glance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceReport<T> {
+    pub time_ms: T,
+    pub distance_record_mm: T,
+    /// Shortest and longest holds that beat the record, `None` when nothing does
+    pub winning_holds: Option<(T, T)>,
+    pub win_count: T,
+    pub best_hold_ms: T,
+    /// `None` when it's too far to fit in T
+    pub best_distance_mm: Option<T>,
+    /// How far the best hold beats the record by, zero when it can't and `None` when the best
+    /// distance doesn't fit
+    pub margin_mm: Option<T>,
+}
+
+const HEADERS: [&str; 9] = [
+    "Race",
+    "Time (ms)",
+    "Record (mm)",
+    "Wins",
+    "Min hold",
+    "Max hold",
+    "Best hold",
+    "Best (mm)",
+    "Margin (mm)",
+];
+
+/// Lays the reports out as a plain text table, one labelled row per race.
+pub fn table<T: Display>(rows: &[(String, RaceReport<T>)]) -> String {
+    let cells = rows
+        .iter()
+        .map(|(label, report)| {
+            let (min_hold, max_hold) = match &report.winning_holds {
+                Some((min_hold, max_hold)) => (min_hold.to_string(), max_hold.to_string()),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            let or_dash = |value: &Option<T>| value.as_ref().map_or("-".to_string(), T::to_string);
+            vec![
+                label.clone(),
+                report.time_ms.to_string(),
+                report.distance_record_mm.to_string(),
+                report.win_count.to_string(),
+                min_hold,
+                max_hold,
+                report.best_hold_ms.to_string(),
+                or_dash(&report.best_distance_mm),
+                or_dash(&report.margin_mm),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    aoc_core::report::table(&HEADERS, &cells)
+}
+
+#[cfg(test)]
+mod test {
+    use super::RaceReport;
+    use crate::{Race, RaceSheet};
+
+    #[test]
+    fn report() {
+        assert_eq!(
+            Race::new(7u64, 9).report(),
+            RaceReport {
+                time_ms: 7,
+                distance_record_mm: 9,
+                winning_holds: Some((2, 5)),
+                win_count: 4,
+                best_hold_ms: 3,
+                best_distance_mm: Some(12),
+                margin_mm: Some(3),
+            }
+        );
+
+        let report = Race::new(30u64, 200).report();
+        assert_eq!(report.winning_holds, Some((11, 19)));
+        assert_eq!(
+            (report.best_hold_ms, report.best_distance_mm),
+            (15, Some(225))
+        );
+        assert_eq!(report.margin_mm, Some(25));
+
+        // The best hold only ties
+        let report = Race::new(10u64, 25).report();
+        assert_eq!(report.winning_holds, None);
+        assert_eq!(report.win_count, 0);
+        assert_eq!(report.margin_mm, Some(0));
+
+        // At the very top the best distance is far too big for the type
+        let report = Race::new(u64::MAX, 0u64).report();
+        assert_eq!(report.best_hold_ms, u64::MAX / 2);
+        assert_eq!((report.best_distance_mm, report.margin_mm), (None, None));
+        assert_eq!(report.win_count, u64::MAX - 1);
+        let report = Race::new(u32::MAX as u64, 0u64).report();
+        assert_eq!(report.best_distance_mm, Some(0x3fff_ffff_8000_0000));
+    }
+
+    #[test]
+    fn table() {
+        let sheet = include_str!("../test_input.txt")
+            .parse::<RaceSheet>()
+            .unwrap();
+        let table = sheet.report_table();
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].trim_start().starts_with("Race | Time (ms)"));
+        assert!(lines[2].trim_start().starts_with("1 |"));
+        assert!(lines[5].contains("kerned"));
+        assert!(lines[5].contains("71503"));
+        // Every row lines up under the headers
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+    }
+}
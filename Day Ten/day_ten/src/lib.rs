@@ -0,0 +1,474 @@
+use aoc_core::{Answer, ParseError, PartError, Solution};
+use colored::*;
+use itertools::Itertools;
+use std::fmt::Display;
+use std::str::FromStr;
+
+const VERTICAL_PIPE: char = '|';
+const HORIZONTAL_PIPE: char = '-';
+const NORTH_EAST_PIPE: char = 'L';
+const NORTH_WEST_PIPE: char = 'J';
+const SOUTH_WEST_PIPE: char = '7';
+const SOUTH_EAST_PIPE: char = 'F';
+const GROUND: char = '.';
+const ANIMAL_START: char = 'S';
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TileState {
+    Undecided,
+    External,
+    Internal,
+    MainLoop,
+}
+
+#[derive(Clone, Debug)]
+struct Tile {
+    char: char,
+    state: TileState,
+    connection_a: Option<usize>,
+    connection_b: Option<usize>,
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let color = match self.state {
+            TileState::Undecided => Color::White,
+            TileState::External => Color::Red,
+            TileState::Internal => Color::Blue,
+            TileState::MainLoop => Color::Green,
+        };
+
+        write!(f, "{}", self.char.to_string().color(color))
+    }
+}
+
+// Phantom types to ensure the correct order of operations
+#[derive(Debug, Clone)]
+pub struct Unfilled {}
+#[derive(Debug, Clone)]
+pub struct MainFilled {}
+#[derive(Debug, Clone)]
+pub struct InternalFilled {}
+#[derive(Debug, Clone)]
+pub struct Filled {}
+
+#[derive(Debug, Clone)]
+pub struct Map<State = Unfilled> {
+    tiles: Vec<Tile>,
+    width: usize,
+    animal_idx: usize,
+    _state: State,
+}
+
+impl<State> Display for Map<State> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, tile) in self.tiles.iter().enumerate() {
+            write!(f, "{}", tile)?;
+            if idx % self.width == self.width - 1 {
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<State> Map<State> {
+    fn get_tile_mut(&mut self, x: isize, y: isize) -> &mut Tile {
+        &mut self.tiles[(y * self.width as isize + x) as usize]
+    }
+
+    fn get_state(&self, x: usize, y: usize) -> TileState {
+        self.tiles[y * self.width + x].state
+    }
+}
+
+impl FromStr for Map<Unfilled> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Map, ParseError> {
+        let rows = aoc_core::parse::lines(10, s).collect_vec();
+        let lines = rows
+            .iter()
+            .map(|line| line.text.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let width = match lines.first() {
+            Some(first) if !first.is_empty() => first.len(),
+            _ => return Err(ParseError::missing_line(10, 1, "a row of pipes")),
+        };
+        // Rows have to line up or the connections end up pointing at the wrong tiles
+        for (line, row) in rows.iter().zip(&lines) {
+            if row.len() != width {
+                let error = line.error(
+                    width.min(row.len()) + 1,
+                    format!("a row {width} tiles wide"),
+                );
+                return Err(match row.get(width) {
+                    Some(extra) => error.found(extra),
+                    None => error.found_end(),
+                });
+            }
+        }
+
+        let mut tiles = Vec::new();
+
+        let upper_conn = |x_idx: usize, y_idx: usize| -> Option<usize> {
+            if y_idx == 0 {
+                None
+            } else {
+                Some((y_idx - 1) * width + x_idx)
+            }
+        };
+
+        let left_conn = |x_idx: usize, y_idx: usize| -> Option<usize> {
+            if x_idx == 0 {
+                None
+            } else {
+                Some(y_idx * width + (x_idx - 1))
+            }
+        };
+
+        let right_conn = |x_idx: usize, y_idx: usize| -> Option<usize> {
+            if x_idx == width - 1 {
+                None
+            } else {
+                Some(y_idx * width + (x_idx + 1))
+            }
+        };
+
+        let lower_conn = |x_idx: usize, y_idx: usize| -> Option<usize> {
+            if y_idx == lines.len() - 1 {
+                None
+            } else {
+                Some((y_idx + 1) * width + x_idx)
+            }
+        };
+
+        let mut animal_idx = None;
+        for (y_idx, y_vec) in lines.iter().enumerate() {
+            for (x_idx, char) in y_vec.iter().enumerate() {
+                let tile = match *char {
+                    VERTICAL_PIPE => Tile {
+                        char: '|',
+                        state: TileState::Undecided,
+                        connection_a: upper_conn(x_idx, y_idx),
+                        connection_b: lower_conn(x_idx, y_idx),
+                    },
+                    HORIZONTAL_PIPE => Tile {
+                        char: '-',
+                        state: TileState::Undecided,
+                        connection_a: right_conn(x_idx, y_idx),
+                        connection_b: left_conn(x_idx, y_idx),
+                    },
+                    NORTH_EAST_PIPE => Tile {
+                        char: 'L',
+                        state: TileState::Undecided,
+                        connection_a: upper_conn(x_idx, y_idx),
+                        connection_b: right_conn(x_idx, y_idx),
+                    },
+                    NORTH_WEST_PIPE => Tile {
+                        char: 'J',
+                        state: TileState::Undecided,
+                        connection_a: upper_conn(x_idx, y_idx),
+                        connection_b: left_conn(x_idx, y_idx),
+                    },
+                    SOUTH_WEST_PIPE => Tile {
+                        char: '7',
+                        state: TileState::Undecided,
+                        connection_a: lower_conn(x_idx, y_idx),
+                        connection_b: left_conn(x_idx, y_idx),
+                    },
+                    SOUTH_EAST_PIPE => Tile {
+                        char: 'F',
+                        state: TileState::Undecided,
+                        connection_a: lower_conn(x_idx, y_idx),
+                        connection_b: right_conn(x_idx, y_idx),
+                    },
+                    GROUND => Tile {
+                        char: '.',
+                        state: TileState::Undecided,
+                        connection_a: None,
+                        connection_b: None,
+                    },
+                    ANIMAL_START => {
+                        if animal_idx.is_some() {
+                            return Err(rows[y_idx]
+                                .error(x_idx + 1, "only one animal start")
+                                .found(char));
+                        }
+                        animal_idx = Some(y_idx * width + x_idx);
+                        Tile {
+                            char: 'S',
+                            state: TileState::MainLoop,
+                            connection_a: None,
+                            connection_b: None,
+                        }
+                    }
+                    _ => {
+                        return Err(rows[y_idx]
+                            .error(x_idx + 1, "a pipe, ground or the animal start")
+                            .found(char))
+                    }
+                };
+
+                tiles.push(tile);
+            }
+        }
+
+        let animal_idx = animal_idx
+            .ok_or_else(|| ParseError::missing_line(10, lines.len() + 1, "an animal start `S`"))?;
+
+        let animal_connections = tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| {
+                tile.connection_a == Some(animal_idx) || tile.connection_b == Some(animal_idx)
+            })
+            .map(|(index, _)| index)
+            .collect_vec();
+
+        // A quick bodge to connect up the animal to the rest of the map
+        let [connection_a, connection_b] = animal_connections[..] else {
+            let (y_idx, x_idx) = (animal_idx / width, animal_idx % width);
+            return Err(rows[y_idx]
+                .error(x_idx + 1, "an animal start connected to exactly two pipes")
+                .found(format!("{} connections", animal_connections.len())));
+        };
+        tiles[animal_idx].connection_a = Some(connection_a);
+        tiles[animal_idx].connection_b = Some(connection_b);
+
+        Ok(Map {
+            tiles,
+            width,
+            animal_idx,
+            _state: Unfilled {},
+        })
+    }
+}
+
+impl Map<Unfilled> {
+    /// Follows the pipes from the animal all the way round, failing on the first pipe that
+    /// doesn't carry the loop on (off the edge, onto ground or into a pipe facing away).
+    pub fn flood_fill_main_loop(mut self) -> Result<Map<MainFilled>, ParseError> {
+        let mut prev_idx = self.animal_idx;
+        let mut cur_idx = self.tiles[self.animal_idx]
+            .connection_a
+            .expect("the animal is always connected");
+        while cur_idx != self.animal_idx {
+            let cur_tile = &mut self.tiles[cur_idx];
+            cur_tile.state = TileState::MainLoop;
+            let next_idx = if cur_tile.connection_a == Some(prev_idx) {
+                cur_tile.connection_b
+            } else {
+                cur_tile.connection_a
+            };
+
+            // The next tile has to lead back here as well or the loop's broken
+            let leads_back = |next_idx: usize| {
+                let next_tile = &self.tiles[next_idx];
+                next_tile.connection_a == Some(cur_idx) || next_tile.connection_b == Some(cur_idx)
+            };
+            match next_idx {
+                Some(next_idx) if leads_back(next_idx) => {
+                    prev_idx = cur_idx;
+                    cur_idx = next_idx;
+                }
+                _ => {
+                    let (y_idx, x_idx) = (cur_idx / self.width, cur_idx % self.width);
+                    return Err(ParseError::new(
+                        10,
+                        y_idx + 1,
+                        x_idx + 1,
+                        "a pipe that carries on the main loop",
+                    )
+                    .found(self.tiles[cur_idx].char));
+                }
+            }
+        }
+
+        Ok(Map {
+            tiles: self.tiles,
+            width: self.width,
+            animal_idx: self.animal_idx,
+            _state: MainFilled {},
+        })
+    }
+}
+
+impl Map<MainFilled> {
+    pub fn main_loop_count(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.state == TileState::MainLoop)
+            .count()
+    }
+
+    // If a ray is fired from a point within a 2d boundary and it crosses the boundary an odd number of times it's inside the boundary
+    pub fn fill_internal(mut self) -> Map<InternalFilled> {
+        let width = self.width;
+        let height = self.tiles.len() / width;
+
+        for (y, x) in (0..height).cartesian_product(0..width) {
+            if self.get_state(x, y) == TileState::MainLoop {
+                continue;
+            }
+            let mut crosses = 0u32;
+            let (mut x2, mut y2) = (x, y);
+
+            while x2 < width && y2 < height {
+                let c2 = self.tiles[y2 * width + x2].char;
+                if self.get_state(x2, y2) == TileState::MainLoop && c2 != 'L' && c2 != '7' {
+                    crosses += 1;
+                }
+                x2 += 1;
+                y2 += 1;
+            }
+
+            if crosses % 2 == 1 {
+                self.get_tile_mut(x as isize, y as isize).state = TileState::Internal;
+            }
+        }
+
+        Map {
+            tiles: self.tiles,
+            width: self.width,
+            animal_idx: self.animal_idx,
+            _state: InternalFilled {},
+        }
+    }
+}
+
+impl Map<InternalFilled> {
+    pub fn main_loop_count(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.state == TileState::MainLoop)
+            .count()
+    }
+
+    pub fn internal_count(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.state == TileState::Internal)
+            .count()
+    }
+
+    pub fn fill_external(mut self) -> Map<Filled> {
+        self.tiles.iter_mut().for_each(|tile| {
+            if tile.state == TileState::Undecided {
+                tile.state = TileState::External;
+            }
+        });
+
+        Map {
+            tiles: self.tiles,
+            width: self.width,
+            animal_idx: self.animal_idx,
+            _state: Filled {},
+        }
+    }
+}
+
+impl Map<Filled> {
+    pub fn main_loop_count(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.state == TileState::MainLoop)
+            .count()
+    }
+
+    pub fn external_count(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.state == TileState::External)
+            .count()
+    }
+
+    pub fn internal_count(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.state == TileState::Internal)
+            .count()
+    }
+}
+
+impl Solution for Map<MainFilled> {
+    const DAY: u8 = 10;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Map::from_str(input)?.flood_fill_main_loop()
+    }
+
+    fn part_one(&self) -> Result<Answer, PartError> {
+        Ok((self.main_loop_count() / 2).into())
+    }
+
+    fn part_two(&self) -> Result<Answer, PartError> {
+        Ok(self.clone().fill_internal().internal_count().into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use aoc_core::Solution;
+
+    use crate::{MainFilled, Map};
+
+    #[test]
+    fn loop_and_inside() {
+        let map = Map::<MainFilled>::parse("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...").unwrap();
+        assert_eq!(map.main_loop_count() / 2, 8);
+    }
+
+    #[test]
+    fn not_square() {
+        let map = Map::<MainFilled>::parse("S---7\n|...|\nL---J").unwrap();
+        assert_eq!(map.part_one(), Ok(6usize.into()));
+        assert_eq!(map.part_two(), Ok(3usize.into()));
+
+        let map = Map::<MainFilled>::parse("S--7\nL--J").unwrap();
+        assert_eq!(map.part_two(), Ok(0usize.into()));
+        let map = Map::<MainFilled>::parse("S7\n||\n||\nLJ").unwrap();
+        assert_eq!(map.part_two(), Ok(0usize.into()));
+    }
+
+    #[test]
+    fn long_loop() {
+        // Far too long a loop to follow one call per pipe
+        let width = 200_000;
+        let input = format!("S{}7\nL{}J", "-".repeat(width - 2), "-".repeat(width - 2));
+        let map = Map::<MainFilled>::parse(&input).unwrap();
+        assert_eq!(map.main_loop_count(), width * 2);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = Map::from_str("..F7.\n.FJ|.\nSJ.X7").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 10, line 3, column 4: expected a pipe, ground or the animal start, found `X`"
+        );
+
+        let err = Map::from_str("..F7.\n.FJ|\nSJ.L7").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+
+        let err = Map::from_str("..F7.\n.FJ|.").unwrap_err();
+        assert_eq!(err.line, 3);
+
+        let err = Map::from_str("S....\n.....").unwrap_err();
+        assert_eq!(err.found.as_deref(), Some("`0 connections`"));
+
+        // The `-` next to the animal runs onto ground
+        let err = Map::<MainFilled>::parse("S-.\n|..\n...").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 10, line 1, column 2: expected a pipe that carries on the main loop, found `-`"
+        );
+        // Off the edge of the map
+        let err = Map::<MainFilled>::parse("S-\n|.").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 2));
+    }
+}
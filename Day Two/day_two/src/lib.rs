@@ -1,4 +1,5 @@
-use anyhow::Context;
+use anyhow::{anyhow, Context};
+use aoc_core::{Answer, ParseError, PartError, Solution};
 use phf::phf_map;
 
 static COLORS: phf::Map<&'static str, CubeColor> = phf_map! {
@@ -7,6 +8,7 @@ static COLORS: phf::Map<&'static str, CubeColor> = phf_map! {
     "blue" => CubeColor::Blue,
 };
 
+#[derive(Debug)]
 pub struct GameHistory {
     pub games: Vec<Game>,
 }
@@ -15,12 +17,31 @@ impl GameHistory {
     pub fn new(log: &str) -> anyhow::Result<GameHistory> {
         let games = log
             .lines()
-            .map(Game::new)
-            .map(|result| result.unwrap())
-            .collect::<Vec<_>>();
+            .enumerate()
+            .map(|(index, line)| Game::new(line).with_context(|| format!("line {}", index + 1)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
         Ok(GameHistory { games })
     }
 
+    /// Like [`GameHistory::new`], but a bad line doesn't sink the whole log.
+    /// Returns every game that parsed along with the errors for the ones that didn't.
+    pub fn new_lenient(log: &str) -> (GameHistory, Vec<LineError>) {
+        let mut games = Vec::new();
+        let mut errors = Vec::new();
+
+        for (index, line) in log.lines().enumerate() {
+            match Game::new(line) {
+                Ok(game) => games.push(game),
+                Err(error) => errors.push(LineError {
+                    line: index + 1,
+                    error,
+                }),
+            }
+        }
+
+        (GameHistory { games }, errors)
+    }
+
     pub fn sum_game_ids_with_cubes(&self, red: u32, green: u32, blue: u32) -> u32 {
         self.games
             .iter()
@@ -34,28 +55,80 @@ impl GameHistory {
     }
 }
 
+impl Solution for GameHistory {
+    const DAY: u8 = 2;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let games = aoc_core::parse::lines(Self::DAY, input)
+            .map(|line| {
+                Game::parse(line.text).map_err(|(token, err)| {
+                    let mut error = line.error(line.column_of(token), "a game record");
+                    // The context chain already says which game, draw and token broke
+                    error.found = Some(format!("{err:#}"));
+                    error
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(GameHistory { games })
+    }
+
+    fn part_one(&self) -> Result<Answer, PartError> {
+        Ok(self.sum_game_ids_with_cubes(12, 13, 14).into())
+    }
+
+    fn part_two(&self) -> Result<Answer, PartError> {
+        Ok(self.sum_min_power().into())
+    }
+}
+
+/// A line that [`GameHistory::new_lenient`] couldn't turn into a game.
+#[derive(Debug)]
+pub struct LineError {
+    /// 1-based line number in the log
+    pub line: usize,
+    pub error: anyhow::Error,
+}
+
+#[derive(Debug)]
 pub struct Game {
     pub id: u32,
     pub draws: Vec<Draw>,
 }
 
+// An error along with the slice of the line it's about
+type Located<'a, T> = Result<T, (&'a str, anyhow::Error)>;
+
 impl Game {
     pub fn new(log: &str) -> anyhow::Result<Game> {
-        let split = log.split(':').collect::<Vec<_>>();
-        let id = Game::get_id(split[0])?;
+        Game::parse(log).map_err(|(_, err)| err)
+    }
 
-        let draws = split[1]
+    fn parse(log: &str) -> Located<'_, Game> {
+        let (id, draws) = log.split_once(':').ok_or_else(|| {
+            let err = anyhow!("missing `:` after the game id in `{log}`");
+            (&log[log.len()..], err)
+        })?;
+        let id = Game::get_id(id).map_err(|err| (id, err))?;
+
+        let draws = draws
             .split(';')
-            .map(Draw::new)
-            .map(|result| result.unwrap())
-            .collect::<Vec<_>>();
+            .enumerate()
+            .map(|(index, draw)| {
+                Draw::parse(draw).map_err(|(token, err)| {
+                    (token, err.context(format!("game {id}, draw {}", index + 1)))
+                })
+            })
+            .collect::<Located<Vec<_>>>()?;
 
         Ok(Game { id, draws })
     }
 
     fn get_id(id_split: &str) -> anyhow::Result<u32> {
-        let id = id_split[5..].parse::<u32>()?;
-        Ok(id)
+        let id = id_split
+            .strip_prefix("Game ")
+            .with_context(|| format!("expected `Game <id>`, found `{id_split}`"))?;
+        id.parse::<u32>()
+            .with_context(|| format!("invalid game id `{id}`"))
     }
 
     pub fn has_enough_cubes(&self, red: u32, green: u32, blue: u32) -> bool {
@@ -110,30 +183,40 @@ pub enum CubeColor {
 
 impl Draw {
     pub fn new(draw_split: &str) -> anyhow::Result<Draw> {
-        let split = draw_split.split(',').collect::<Vec<_>>();
+        Draw::parse(draw_split).map_err(|(_, err)| err)
+    }
+
+    fn parse(draw_split: &str) -> Located<'_, Draw> {
         let mut red = 0;
         let mut blue = 0;
         let mut green = 0;
 
-        split
-            .into_iter()
-            .map(Draw::get_color)
-            .map(|result| result.unwrap())
-            .for_each(|(amount, color)| match color {
+        for token in draw_split.split(',').map(str::trim) {
+            let (amount, color) = Draw::get_color(token)
+                .map_err(|err| (token, err.context(format!("cube count `{token}`"))))?;
+            match color {
                 CubeColor::Red => red += amount,
                 CubeColor::Green => green += amount,
                 CubeColor::Blue => blue += amount,
-            });
+            }
+        }
 
         Ok(Draw { red, green, blue })
     }
 
     fn get_color(color: &str) -> anyhow::Result<(u32, CubeColor)> {
-        let split = color.split(' ').collect::<Vec<_>>();
+        let (amount, color) = color
+            .trim()
+            .split_once(' ')
+            .context("expected `<count> <color>`")?;
 
-        let amount = split[1].parse()?;
+        let amount = amount
+            .parse()
+            .with_context(|| format!("invalid count `{amount}`"))?;
 
-        let color = COLORS.get(split[2]).context("Invalid color")?;
+        let color = COLORS
+            .get(color)
+            .ok_or_else(|| anyhow!("unknown color `{color}`"))?;
 
         Ok((amount, *color))
     }
@@ -141,4 +224,4 @@ impl Draw {
     pub fn has_enough_cubes(&self, red: u32, green: u32, blue: u32) -> bool {
         self.red <= red && self.green <= green && self.blue <= blue
     }
-}
\ No newline at end of file
+}
@@ -1,25 +1,7 @@
 use day_two::GameHistory;
 
 fn main() {
-    let start = std::time::Instant::now();
-    let game = include_str!("input_daytwo.txt");
-    let game = GameHistory::new(game).unwrap();
-    let sum = game.sum_game_ids_with_cubes(12, 13, 14);
-    println!(
-        "Part 1 - Sum of game ids: {} in {} micro seconds",
-        sum,
-        start.elapsed().as_micros()
-    );
-
-    let part_2_start = std::time::Instant::now();
-    let min_power = game.sum_min_power();
-    println!(
-        "Part 2 - Sum of minimum power: {} in {} micro seconds",
-        min_power,
-        part_2_start.elapsed().as_micros()
-    );
-
-    println!("Total time: {} micro seconds", start.elapsed().as_micros());
+    aoc_core::main::<GameHistory>();
 }
 
 #[cfg(test)]
@@ -107,4 +89,64 @@ mod test {
 
         assert_eq!(history.sum_min_power(), 2286);
     }
+
+    #[test]
+    fn test_errors() {
+        let err = day_two::Game::new("Game 3: 1 red; 4 purple, 2 blue").unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "game 3, draw 2: cube count `4 purple`: unknown color `purple`"
+        );
+
+        let err = day_two::Game::new("Gme 3: 1 red").unwrap_err();
+        assert_eq!(format!("{err}"), "expected `Game <id>`, found `Gme 3`");
+
+        let err = day_two::Game::new("Game 3 1 red").unwrap_err();
+        assert!(format!("{err}").starts_with("missing `:`"));
+
+        let err = day_two::Draw::new(" 3blue").unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "cube count `3blue`: expected `<count> <color>`"
+        );
+
+        let err = super::GameHistory::new("Game 1: 1 red\nGame 2: x red").unwrap_err();
+        assert!(format!("{err:#}").starts_with("line 2: game 2, draw 1: cube count `x red`"));
+    }
+
+    #[test]
+    fn test_error_columns() {
+        use aoc_core::Solution;
+
+        // Each error points at the part of the line that broke
+        let err = super::GameHistory::parse("Game 1: 1 red\nGame 3: 1 red; 4 purple, 2 blue")
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 16));
+        assert_eq!(
+            err.to_string(),
+            "day 2, line 2, column 16: expected a game record, \
+             found game 3, draw 2: cube count `4 purple`: unknown color `purple`"
+        );
+
+        let err = super::GameHistory::parse("Game 3: 1 red,3blue").unwrap_err();
+        assert_eq!(err.column, 15);
+        let err = super::GameHistory::parse("Gme 3: 1 red").unwrap_err();
+        assert_eq!(err.column, 1);
+        let err = super::GameHistory::parse("Game 3 1 red").unwrap_err();
+        assert_eq!(err.column, 13);
+    }
+
+    #[test]
+    fn test_lenient() {
+        let log = "Game 1: 1 red\nGame 2: x red\nGame 3: 2 blue; 1 green\nnonsense";
+        let (history, errors) = super::GameHistory::new_lenient(log);
+        assert_eq!(
+            history.games.iter().map(|game| game.id).collect::<Vec<_>>(),
+            [1, 3]
+        );
+        assert_eq!(
+            errors.iter().map(|error| error.line).collect::<Vec<_>>(),
+            [2, 4]
+        );
+    }
 }
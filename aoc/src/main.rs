@@ -0,0 +1,141 @@
+use aoc_core::{run, Part, RunError};
+
+const USAGE: &str = "usage: aoc run --day <1-10> [--part <1|2>] [--input <path|->]
+       aoc run --all [--part <1|2>]
+
+Inputs are read from inputs/dayNN.txt unless --input is given, `-` reads stdin.";
+
+type Runner = fn(&str, Part) -> Result<(), RunError>;
+
+// Every day's runner, indexed by day - 1
+const DAYS: [Runner; 10] = [
+    run::<day_one::CalibrationDocument>,
+    run::<day_two::GameHistory>,
+    run::<day_three::CharGrid>,
+    run::<day_four::ScoreGenerator>,
+    run::<day_five::Almanac>,
+    run::<day_six::RaceSheet<day_six::BigUint>>,
+    run::<day_seven::CamelCards>,
+    run::<day_eight::GuidedMap>,
+    run::<day_nine::HistoryGrid>,
+    run::<day_ten::Map<day_ten::MainFilled>>,
+];
+
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    days: Vec<u8>,
+    part: Part,
+    input: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    match args.next().as_deref() {
+        Some("run") => {}
+        Some(command) => return Err(format!("unknown command `{command}`")),
+        None => return Err("missing command".to_string()),
+    }
+
+    let mut days = None;
+    let mut part = Part::Both;
+    let mut input = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => days = Some((1..=DAYS.len() as u8).collect()),
+            "--day" => {
+                let day = args
+                    .next()
+                    .and_then(|day| day.parse::<u8>().ok())
+                    .filter(|day| (1..=DAYS.len() as u8).contains(day))
+                    .ok_or_else(|| format!("--day expects a day from 1 to {}", DAYS.len()))?;
+                days = Some(vec![day]);
+            }
+            "--part" => {
+                part = args
+                    .next()
+                    .and_then(|part| part.parse::<u8>().ok())
+                    .and_then(Part::from_number)
+                    .ok_or("--part expects 1 or 2")?;
+            }
+            "--input" => input = Some(args.next().ok_or("--input expects a path or `-`")?),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    let days: Vec<u8> = days.ok_or("expected either --day or --all")?;
+    if input.is_some() && days.len() > 1 {
+        return Err("--input can only be used with a single --day".to_string());
+    }
+    Ok(Args { days, part, input })
+}
+
+fn main() {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
+    let mut failed = false;
+    for day in args.days {
+        let result = match aoc_core::input::load(day, args.input.as_deref()) {
+            Ok(input) => DAYS[day as usize - 1](&input, args.part).map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+        if let Err(err) = result {
+            eprintln!("{err}");
+            failed = true;
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_args, Args};
+    use aoc_core::Part;
+
+    fn parse(args: &str) -> Result<Args, String> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn single_day() {
+        assert_eq!(
+            parse("run --day 7 --part 2"),
+            Ok(Args {
+                days: vec![7],
+                part: Part::Two,
+                input: None,
+            })
+        );
+    }
+
+    #[test]
+    fn all_days() {
+        let args = parse("run --all").unwrap();
+        assert_eq!(args.days, (1..=10).collect::<Vec<_>>());
+        assert_eq!(args.part, Part::Both);
+    }
+
+    #[test]
+    fn input_path() {
+        let args = parse("run --day 3 --input my_input.txt").unwrap();
+        assert_eq!(args.input.as_deref(), Some("my_input.txt"));
+        assert!(parse("run --all --input my_input.txt").is_err());
+        assert!(parse("run --day 3 --input").is_err());
+    }
+
+    #[test]
+    fn bad_args() {
+        assert!(parse("").is_err());
+        assert!(parse("walk --all").is_err());
+        assert!(parse("run").is_err());
+        assert!(parse("run --day 11").is_err());
+        assert!(parse("run --day 1 --part 3").is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Where the conventional input for `day` lives, relative to the working directory.
+pub fn default_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("inputs/day{day:02}.txt"))
+}
+
+/// The conventional input for `day` resolved against the workspace root instead of the working
+/// directory, for tests that check against the real puzzle input when it's been put there.
+pub fn workspace_input(day: u8) -> Option<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(default_path(day));
+    std::fs::read_to_string(path).ok()
+}
+
+/// Loads the puzzle input for `day`.
+///
+/// `arg` is what the user passed on the command line, `-` reads from stdin, any other value is
+/// treated as a path and no argument at all falls back to [`default_path`].
+pub fn load(day: u8, arg: Option<&str>) -> Result<String, InputError> {
+    match arg {
+        Some("-") => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(InputError::Stdin)?;
+            Ok(input)
+        }
+        Some(path) => read(Path::new(path)),
+        None => {
+            let path = default_path(day);
+            if path.exists() {
+                read(&path)
+            } else {
+                Err(InputError::Missing { day, path })
+            }
+        }
+    }
+}
+
+/// Loads the input for `day` from the first command line argument, exiting with a readable
+/// message rather than a panic when it can't be found.
+pub fn from_args(day: u8) -> String {
+    let arg = std::env::args().nth(1);
+    match load(day, arg.as_deref()) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<String, InputError> {
+    std::fs::read_to_string(path).map_err(|source| InputError::Read {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// No input was given and there's nothing at the conventional location either
+    Missing {
+        day: u8,
+        path: PathBuf,
+    },
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Stdin(std::io::Error),
+}
+
+impl std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing { day, path } => write!(
+                f,
+                "no input for day {day}: {} does not exist (pass a path, or `-` to read stdin)",
+                path.display()
+            ),
+            InputError::Read { path, source } => {
+                write!(f, "could not read {}: {source}", path.display())
+            }
+            InputError::Stdin(source) => write!(f, "could not read input from stdin: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Missing { .. } => None,
+            InputError::Read { source, .. } | InputError::Stdin(source) => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::InputError;
+
+    #[test]
+    fn default_path() {
+        assert_eq!(super::default_path(7).to_str(), Some("inputs/day07.txt"));
+        assert_eq!(super::default_path(10).to_str(), Some("inputs/day10.txt"));
+    }
+
+    #[test]
+    fn missing_file() {
+        let err = super::load(7, Some("does/not/exist.txt")).unwrap_err();
+        assert!(matches!(err, InputError::Read { .. }));
+        assert!(err
+            .to_string()
+            .starts_with("could not read does/not/exist.txt"));
+    }
+
+    #[test]
+    fn reads_path() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+        let input = super::load(1, Some(path)).unwrap();
+        assert!(input.contains("name = \"aoc_core\""));
+    }
+}
@@ -0,0 +1,13 @@
+//! Shared plumbing for the Advent of Code 2023 solvers.
+//!
+//! Every day used to carry its own copy of the timing, reporting and number parsing code,
+//! this crate is where that lives now so the days only have to worry about the puzzle.
+
+pub mod input;
+pub mod parse;
+pub mod report;
+pub mod solution;
+
+pub use parse::{Line, ParseError};
+pub use report::{part, timed};
+pub use solution::{main, main_or_show, run, Answer, Part, PartError, RunError, Solution};
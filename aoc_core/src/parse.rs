@@ -0,0 +1,199 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A malformed puzzle input, pointing at the exact day, line and column that didn't make sense.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1 based line number
+    pub line: usize,
+    /// 1 based column, counted in characters
+    pub column: usize,
+    pub expected: String,
+    pub found: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(day: u8, line: usize, column: usize, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            day,
+            line,
+            column,
+            expected: expected.into(),
+            found: None,
+        }
+    }
+
+    /// Records the token that was found instead of what was expected.
+    pub fn found(mut self, found: impl Display) -> ParseError {
+        self.found = Some(format!("`{found}`"));
+        self
+    }
+
+    /// Records that the line ran out before the expected token.
+    pub fn found_end(mut self) -> ParseError {
+        self.found = Some("the end of the line".to_string());
+        self
+    }
+
+    /// An error for input that ran out before reaching `line`.
+    pub fn missing_line(day: u8, line: usize, expected: impl Into<String>) -> ParseError {
+        let mut error = ParseError::new(day, line, 1, expected);
+        error.found = Some("the end of the input".to_string());
+        error
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )?;
+        if let Some(found) = &self.found {
+            write!(f, ", found {found}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Every line of `input` along with its line number, ready to be parsed.
+pub fn lines(day: u8, input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(move |(index, text)| Line::new(day, index + 1, text))
+}
+
+/// A single line of puzzle input that knows where it came from, so parsers can report errors
+/// against the slice of the line they were looking at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub day: u8,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(day: u8, number: usize, text: &'a str) -> Line<'a> {
+        Line { day, number, text }
+    }
+
+    /// The column `part` starts at, `part` must be a slice of this line.
+    pub fn column_of(&self, part: &str) -> usize {
+        let offset = (part.as_ptr() as usize)
+            .saturating_sub(self.text.as_ptr() as usize)
+            .min(self.text.len());
+        self.text
+            .get(..offset)
+            .map_or(offset, |before| before.chars().count())
+            + 1
+    }
+
+    pub fn error(&self, column: usize, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self.day, self.number, column, expected)
+    }
+
+    /// An error pointing at `part`, a slice of this line that wasn't what was expected.
+    pub fn error_at(&self, part: &str, expected: impl Into<String>) -> ParseError {
+        let error = self.error(self.column_of(part), expected);
+        if part.is_empty() {
+            error.found_end()
+        } else {
+            error.found(part)
+        }
+    }
+
+    /// An error for a line that stopped short of something that was expected.
+    pub fn error_at_end(&self, expected: impl Into<String>) -> ParseError {
+        self.error(self.text.chars().count() + 1, expected)
+            .found_end()
+    }
+
+    /// Strips `prefix` from the start of `part`, which must be a slice of this line.
+    pub fn expect_prefix(&self, part: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        part.strip_prefix(prefix)
+            .ok_or_else(|| self.error_at(part, format!("`{prefix}`")))
+    }
+
+    /// Splits `part` at the first `delimiter`, which must be there.
+    pub fn split_once(
+        &self,
+        part: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        part.split_once(delimiter)
+            .ok_or_else(|| self.error_at(&part[part.len()..], format!("`{delimiter}`")))
+    }
+
+    /// Parses a single token, which must be a slice of this line.
+    pub fn parse<T: FromStr>(&self, token: &'a str, expected: &str) -> Result<T, ParseError> {
+        token.parse().map_err(|_| self.error_at(token, expected))
+    }
+
+    /// Parses every whitespace separated token in `part` as a number.
+    pub fn numbers<T: FromStr>(&self, part: &'a str) -> Result<Vec<T>, ParseError> {
+        part.split_whitespace()
+            .map(|token| self.parse(token, "a number"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Line, ParseError};
+
+    #[test]
+    fn numbers() {
+        let line = Line::new(4, 1, " 41 48  83 86 17 ");
+        assert_eq!(line.numbers::<u32>(line.text), Ok(vec![41, 48, 83, 86, 17]));
+
+        let line = Line::new(9, 3, "10 -3 0");
+        assert_eq!(line.numbers::<i64>(line.text), Ok(vec![10, -3, 0]));
+    }
+
+    #[test]
+    fn bad_number_column() {
+        let line = Line::new(9, 2, "1  two 3");
+        let err = line.numbers::<u32>(line.text).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError {
+                day: 9,
+                line: 2,
+                column: 4,
+                expected: "a number".to_string(),
+                found: Some("`two`".to_string()),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "day 9, line 2, column 4: expected a number, found `two`"
+        );
+    }
+
+    #[test]
+    fn prefix_and_split() {
+        let line = Line::new(6, 1, "Time:      7  15   30");
+        let rest = line.expect_prefix(line.text, "Time:").unwrap();
+        assert_eq!(line.column_of(rest), 6);
+
+        let err = line.expect_prefix(line.text, "Distance:").unwrap_err();
+        assert_eq!(err.column, 1);
+
+        let err = line.split_once(line.text, "|").unwrap_err();
+        assert_eq!(err.column, 22);
+        assert_eq!(err.found.as_deref(), Some("the end of the line"));
+    }
+
+    #[test]
+    fn lines_are_numbered_from_one() {
+        let numbers = super::lines(1, "a\nb\nc")
+            .map(|line| line.number)
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, vec![1, 2, 3]);
+    }
+}
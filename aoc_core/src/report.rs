@@ -0,0 +1,83 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// Runs `f` and returns its result alongside how long it took.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Prints an answer in the shared `Part N: answer (elapsed)` format.
+pub fn print_part(part: u8, answer: impl Display, elapsed: Duration) {
+    println!("Part {part}: {answer} ({elapsed:?})");
+}
+
+/// Times `solve` and prints its answer, returning the answer in case the caller needs it again.
+pub fn part<T: Display>(part: u8, solve: impl FnOnce() -> T) -> T {
+    let (answer, elapsed) = timed(solve);
+    print_part(part, &answer, elapsed);
+    answer
+}
+
+/// Lays `rows` out as a plain text table under `headers`, every column right-aligned to its widest
+/// cell and a rule of dashes under the headers.
+pub fn table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let widths = (0..headers.len())
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.chars().count())
+                .chain([headers[column].chars().count()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let mut table = String::new();
+    let mut push_row = |row: &mut dyn Iterator<Item = &str>| {
+        let line = row
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:>width$}"))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        table.push_str(&line);
+        table.push('\n');
+    };
+
+    push_row(&mut headers.iter().copied());
+    let rule = widths
+        .iter()
+        .map(|&width| "-".repeat(width))
+        .collect::<Vec<_>>();
+    push_row(&mut rule.iter().map(String::as_str));
+    for row in rows {
+        push_row(&mut row.iter().map(String::as_str));
+    }
+    table
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn timed_returns_result() {
+        let (result, _) = super::timed(|| 2 + 2);
+        assert_eq!(result, 4);
+    }
+
+    #[test]
+    fn table() {
+        let rows = [
+            vec!["a".to_string(), "12345".to_string()],
+            vec!["bcdef".to_string(), "6".to_string()],
+        ];
+        assert_eq!(
+            super::table(&["Name", "N"], &rows),
+            " Name |     N\n\
+             ----- | -----\n\
+             \x20   a | 12345\n\
+             bcdef |     6\n"
+        );
+        assert_eq!(super::table(&["Only"], &[]), "Only\n----\n");
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt::Display;
+
+use crate::parse::ParseError;
+use crate::{input, report};
+
+/// A single day's puzzle, parsed once and then asked for both of its answers.
+pub trait Solution: Sized {
+    /// The day of the advent calendar this solves.
+    const DAY: u8;
+
+    fn parse(input: &str) -> Result<Self, ParseError>;
+    fn part_one(&self) -> Result<Answer, PartError>;
+    fn part_two(&self) -> Result<Answer, PartError>;
+}
+
+/// Which of a day's parts to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn from_number(part: u8) -> Option<Part> {
+        match part {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    fn includes(self, part: Part) -> bool {
+        self == Part::Both || self == part
+    }
+}
+
+/// The answer to a part, kept typed so callers can compare it without going through strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(answer) => write!(f, "{answer}"),
+            Answer::Signed(answer) => write!(f, "{answer}"),
+            Answer::Text(answer) => write!(f, "{answer}"),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident: $($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(answer: $ty) -> Answer {
+                    Answer::$variant(answer as _)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Unsigned: u32, u64, usize);
+answer_from!(Signed: i32, i64);
+
+impl From<String> for Answer {
+    fn from(answer: String) -> Answer {
+        Answer::Text(answer)
+    }
+}
+
+/// Why a part couldn't come up with an answer for input that parsed fine.
+///
+/// Anything that implements `std::error::Error` converts into one, so parts can use `?`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartError(pub String);
+
+impl<E: std::error::Error> From<E> for PartError {
+    fn from(err: E) -> PartError {
+        PartError(err.to_string())
+    }
+}
+
+impl Display for PartError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Everything that went wrong running a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    Parse(ParseError),
+    /// Every part that failed, by part number
+    Parts {
+        day: u8,
+        failed: Vec<(u8, PartError)>,
+    },
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::Parse(err) => write!(f, "{err}"),
+            RunError::Parts { day, failed } => {
+                let lines = failed
+                    .iter()
+                    .map(|(part, err)| format!("day {day}, part {part}: {err}"))
+                    .collect::<Vec<_>>();
+                write!(f, "{}", lines.join("\n"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+impl From<ParseError> for RunError {
+    fn from(err: ParseError) -> RunError {
+        RunError::Parse(err)
+    }
+}
+
+type PartFn<S> = fn(&S) -> Result<Answer, PartError>;
+
+/// Parses `input` as day `S` and prints the requested parts in the shared report format.
+/// Parts that fail aren't printed, they come back in the error instead.
+pub fn run<S: Solution>(input: &str, part: Part) -> Result<(), RunError> {
+    let (solution, elapsed) = report::timed(|| S::parse(input));
+    let solution = solution?;
+    println!("Day {} parsed ({elapsed:?})", S::DAY);
+
+    let mut failed = Vec::new();
+    let parts: [(u8, Part, PartFn<S>); 2] =
+        [(1, Part::One, S::part_one), (2, Part::Two, S::part_two)];
+    for (number, which, solve) in parts {
+        if !part.includes(which) {
+            continue;
+        }
+        match report::timed(|| solve(&solution)) {
+            (Ok(answer), elapsed) => report::print_part(number, answer, elapsed),
+            (Err(err), _) => failed.push((number, err)),
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(RunError::Parts {
+            day: S::DAY,
+            failed,
+        })
+    }
+}
+
+/// Entry point for a single day's binary, runs both parts against the input named on the command
+/// line (see [`input::load`]).
+pub fn main<S: Solution>() {
+    let input = input::from_args(S::DAY);
+    if let Err(err) = run::<S>(&input, Part::Both) {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+}
+
+/// Like [`main`], except that when `flag` is on the command line it prints what `show` makes of
+/// the input instead of the answers.
+pub fn main_or_show<S: Solution>(
+    flag: &str,
+    show: impl FnOnce(&str) -> Result<String, ParseError>,
+) {
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    let Some(position) = args.iter().position(|arg| arg == flag) else {
+        return main::<S>();
+    };
+    args.remove(position);
+
+    let shown = input::load(S::DAY, args.first().map(String::as_str))
+        .map_err(|err| err.to_string())
+        .and_then(|input| show(&input).map_err(|err| err.to_string()));
+    match shown {
+        Ok(text) => print!("{text}"),
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{run, Answer, Part, PartError, RunError, Solution};
+    use crate::ParseError;
+
+    // Part one always works, part two never does
+    struct HalfDone;
+
+    impl Solution for HalfDone {
+        const DAY: u8 = 25;
+
+        fn parse(_: &str) -> Result<Self, ParseError> {
+            Ok(HalfDone)
+        }
+
+        fn part_one(&self) -> Result<Answer, PartError> {
+            Ok(1u32.into())
+        }
+
+        fn part_two(&self) -> Result<Answer, PartError> {
+            Err(PartError("no star for you".to_string()))
+        }
+    }
+
+    #[test]
+    fn failed_parts() {
+        assert_eq!(run::<HalfDone>("", Part::One), Ok(()));
+        let err = run::<HalfDone>("", Part::Both).unwrap_err();
+        assert_eq!(
+            err,
+            RunError::Parts {
+                day: 25,
+                failed: vec![(2, PartError("no star for you".to_string()))]
+            }
+        );
+        assert_eq!(err.to_string(), "day 25, part 2: no star for you");
+    }
+
+    #[test]
+    fn answer_display() {
+        assert_eq!(Answer::from(6440u32).to_string(), "6440");
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(Answer::from(String::from("AAA")).to_string(), "AAA");
+    }
+
+    #[test]
+    fn part_includes() {
+        assert!(Part::Both.includes(Part::One));
+        assert!(Part::Two.includes(Part::Two));
+        assert!(!Part::One.includes(Part::Two));
+        assert_eq!(Part::from_number(3), None);
+    }
+}